@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_spine::{
+    SkeletonData, SkeletonDataHandle, SpineAnimationPlayer, SpineEntityCommandsExt, SpinePlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SpinePlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, controls)
+        .run();
+}
+
+fn setup(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    mut skeletons: ResMut<Assets<SkeletonData>>,
+) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::new("Space to jump, S to stop, W to walk"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12.),
+            left: px(12.),
+            ..default()
+        },
+    ));
+
+    let skeleton = SkeletonData::new_from_json(
+        asset_server.load("spineboy/export/spineboy-pro.json"),
+        asset_server.load("spineboy/export/spineboy-pma.atlas"),
+    );
+    let skeleton_handle = skeletons.add(skeleton);
+
+    // animations requested before the skeleton is loaded are applied once it is ready
+    commands
+        .spawn((
+            SkeletonDataHandle(skeleton_handle.clone()),
+            Transform::from_xyz(0., -200., 0.).with_scale(Vec3::ONE * 0.5),
+        ))
+        .play_spine("walk")
+        .looping();
+}
+
+fn controls(mut player_query: Query<&mut SpineAnimationPlayer>, keys: Res<ButtonInput<KeyCode>>) {
+    for mut player in player_query.iter_mut() {
+        if keys.just_pressed(KeyCode::Space) {
+            player.play("jump").mix(0.1);
+            player.queue("walk").looping().mix(0.2);
+        }
+        if keys.just_pressed(KeyCode::KeyS) {
+            player.stop(0, 0.3);
+        }
+        if keys.just_pressed(KeyCode::KeyW) {
+            player.play("walk").looping().mix(0.3);
+        }
+    }
+}
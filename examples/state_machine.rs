@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_spine::{SkeletonData, SkeletonDataHandle, SpinePlugin, SpineStateMachine, SpineSystem};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SpinePlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, control.before(SpineSystem::UpdateAnimation))
+        .run();
+}
+
+fn setup(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    mut skeletons: ResMut<Assets<SkeletonData>>,
+) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::new("Hold D to walk, Shift + D to run, Space to jump"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12.),
+            left: px(12.),
+            ..default()
+        },
+    ));
+
+    let skeleton = SkeletonData::new_from_json(
+        asset_server.load("spineboy/export/spineboy-pro.json"),
+        asset_server.load("spineboy/export/spineboy-pma.atlas"),
+    );
+    let skeleton_handle = skeletons.add(skeleton);
+
+    commands.spawn((
+        SkeletonDataHandle(skeleton_handle.clone()),
+        SpineStateMachine::new(asset_server.load("spineboy/spineboy.spinesm.ron")),
+        Transform::from_xyz(0., -200., 0.).with_scale(Vec3::ONE * 0.5),
+    ));
+}
+
+fn control(
+    mut state_machine_query: Query<&mut SpineStateMachine>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    let mut speed = 0.;
+    if keys.pressed(KeyCode::KeyD) {
+        speed = if keys.pressed(KeyCode::ShiftLeft) {
+            1.
+        } else {
+            0.5
+        };
+    }
+    for mut state_machine in state_machine_query.iter_mut() {
+        state_machine.set_float("speed", speed);
+        if keys.just_pressed(KeyCode::Space) {
+            state_machine.set_trigger("jump");
+        }
+    }
+}
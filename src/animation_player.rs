@@ -0,0 +1,443 @@
+//! Convenient animation playback for Spine entities.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use rusty_spine::{AnimationState, SpineError};
+
+use crate::{Spine, lod::SpineLodState};
+
+/// Buffers animation playback requests for a Spine entity.
+///
+/// Requests are applied in order before
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation). Requests made before the
+/// skeleton is ready are kept until it is, and applied before the first update, so animations can
+/// be started right when spawning:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands
+///     .spawn(SkeletonDataHandle::default())
+///     .play_spine("run")
+///     .looping()
+///     .on_track(1)
+///     .mix(0.2);
+/// # }
+/// ```
+///
+/// This component is added automatically alongside
+/// [`SkeletonDataHandle`](crate::SkeletonDataHandle) and can also be used directly from systems:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// fn jump(mut player_query: Query<&mut SpineAnimationPlayer>) {
+///     for mut player in player_query.iter_mut() {
+///         player.play("jump").mix(0.1);
+///         player.queue("idle").looping().mix(0.2);
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct SpineAnimationPlayer {
+    pending: VecDeque<SpinePlayback>,
+}
+
+impl SpineAnimationPlayer {
+    /// Play an animation, replacing the current animation on the track.
+    pub fn play(&mut self, animation: &str) -> &mut SpinePlay {
+        self.pending
+            .push_back(SpinePlayback::Play(SpinePlay::new(animation)));
+        let Some(SpinePlayback::Play(play)) = self.pending.back_mut() else {
+            unreachable!();
+        };
+        play
+    }
+
+    /// Queue an animation to play after the current animation on the track.
+    pub fn queue(&mut self, animation: &str) -> &mut SpinePlay {
+        self.pending
+            .push_back(SpinePlayback::Queue(SpinePlay::new(animation)));
+        let Some(SpinePlayback::Queue(play)) = self.pending.back_mut() else {
+            unreachable!();
+        };
+        play
+    }
+
+    /// Clear a track immediately, leaving bones in their current pose.
+    pub fn clear(&mut self, track: usize) {
+        self.pending.push_back(SpinePlayback::Clear(Some(track)));
+    }
+
+    /// Clear every track immediately, leaving bones in their current pose.
+    pub fn clear_all(&mut self) {
+        self.pending.push_back(SpinePlayback::Clear(None));
+    }
+
+    /// Mix a track out to the setup pose over `mix` seconds.
+    pub fn stop(&mut self, track: usize, mix: f32) {
+        self.pending.push_back(SpinePlayback::Stop {
+            track: Some(track),
+            mix,
+        });
+    }
+
+    /// Mix every track out to the setup pose over `mix` seconds.
+    pub fn stop_all(&mut self, mix: f32) {
+        self.pending
+            .push_back(SpinePlayback::Stop { track: None, mix });
+    }
+
+    /// Set the time of a track and pose the skeleton immediately, see [`SpineSeek`].
+    pub fn seek(&mut self, seek: SpineSeek) {
+        self.pending.push_back(SpinePlayback::Seek(seek));
+    }
+
+    pub fn push(&mut self, playback: SpinePlayback) {
+        self.pending.push_back(playback);
+    }
+
+    /// Requests which have not been applied yet.
+    pub fn pending(&self) -> impl Iterator<Item = &SpinePlayback> {
+        self.pending.iter()
+    }
+}
+
+/// A playback request, see [`SpineAnimationPlayer`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum SpinePlayback {
+    /// Play an animation, replacing the current animation on the track.
+    Play(SpinePlay),
+    /// Queue an animation to play after the current animation on the track.
+    Queue(SpinePlay),
+    /// Clear a track, or every track if `None`.
+    Clear(Option<usize>),
+    /// Mix a track out to the setup pose, or every track if `None`.
+    Stop { track: Option<usize>, mix: f32 },
+    /// Set the time of a track.
+    Seek(SpineSeek),
+}
+
+impl SpinePlayback {
+    pub fn apply(&self, animation_state: &mut AnimationState) -> Result<(), SpineError> {
+        match self {
+            SpinePlayback::Play(play) => {
+                let mut track_entry = animation_state.set_animation_by_name(
+                    play.track,
+                    &play.animation,
+                    play.looping,
+                )?;
+                if let Some(mix) = play.mix {
+                    track_entry.set_mix_duration(mix);
+                }
+            }
+            SpinePlayback::Queue(play) => {
+                let mut track_entry = animation_state.add_animation_by_name(
+                    play.track,
+                    &play.animation,
+                    play.looping,
+                    play.delay,
+                )?;
+                if let Some(mix) = play.mix {
+                    track_entry.set_mix_duration(mix);
+                }
+            }
+            SpinePlayback::Clear(Some(track)) => {
+                animation_state.clear_track(*track);
+            }
+            SpinePlayback::Clear(None) => {
+                animation_state.clear_tracks();
+            }
+            SpinePlayback::Stop {
+                track: Some(track),
+                mix,
+            } => {
+                animation_state.set_empty_animation(*track, *mix);
+            }
+            SpinePlayback::Stop { track: None, mix } => {
+                animation_state.set_empty_animations(*mix);
+            }
+            SpinePlayback::Seek(seek) => {
+                if let Some(mut track_entry) = animation_state.track_at_index_mut(seek.track) {
+                    track_entry.set_track_time(seek.time);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An animation to play, see [`SpineAnimationPlayer::play`] and [`SpineAnimationPlayer::queue`].
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpinePlay {
+    pub animation: String,
+    pub track: usize,
+    pub looping: bool,
+    /// The mix duration from the previous animation, or `None` to use
+    /// [`Crossfades`](crate::Crossfades).
+    pub mix: Option<f32>,
+    /// Seconds to wait after the previous animation starts, only used when queueing. If zero or
+    /// less, the animation plays relative to the end of the previous animation.
+    pub delay: f32,
+}
+
+impl SpinePlay {
+    pub fn new(animation: &str) -> Self {
+        Self {
+            animation: animation.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn looping(&mut self) -> &mut Self {
+        self.looping = true;
+        self
+    }
+
+    pub fn on_track(&mut self, track: usize) -> &mut Self {
+        self.track = track;
+        self
+    }
+
+    pub fn mix(&mut self, mix: f32) -> &mut Self {
+        self.mix = Some(mix);
+        self
+    }
+
+    pub fn delay(&mut self, delay: f32) -> &mut Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Sets the time of a track, and poses the skeleton at that time in the same frame, for example to
+/// scrub through an animation in an editor or to restore a replay.
+///
+/// The skeleton is posed and its meshes rebuilt in the next
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation) and
+/// [`SpineSystem::UpdateMeshes`](crate::SpineSystem::UpdateMeshes), even if it is
+/// [`SpinePaused`] or skipped by a [`SpineLod`](crate::SpineLod). Seeking an empty track does
+/// nothing.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineSeek;
+/// fn scrub(mut commands: Commands, spine_query: Query<Entity, With<Spine>>) {
+///     for entity in spine_query.iter() {
+///         commands
+///             .entity(entity)
+///             .insert(SpinePaused)
+///             .seek_spine(SpineSeek::new(0, 1.5).suppress_events());
+///     }
+/// }
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineSeek {
+    pub track: usize,
+    /// The new track time, in seconds. For looping animations, this can be greater than the
+    /// animation duration.
+    pub time: f32,
+    /// If true, events fired while posing the skeleton at the new time are dropped, including
+    /// events from other requests applied in the same frame.
+    pub suppress_events: bool,
+}
+
+impl SpineSeek {
+    pub fn new(track: usize, time: f32) -> Self {
+        Self {
+            track,
+            time,
+            suppress_events: false,
+        }
+    }
+
+    pub fn suppress_events(mut self) -> Self {
+        self.suppress_events = true;
+        self
+    }
+}
+
+/// Pauses a Spine entity while present. Time does not accumulate while paused, and the skeleton is
+/// only posed again after a [`SpineSeek`]. Meshes are still rebuilt, so changes made to the paused
+/// skeleton, for example through [`SpineSync`](crate::SpineSync), are shown.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpinePaused;
+
+/// Spine playback extensions for [`EntityCommands`], see [`SpineAnimationPlayer`].
+pub trait SpineEntityCommandsExt {
+    /// Play an animation, replacing the current animation on the track.
+    fn play_spine(&mut self, animation: &str) -> SpinePlayCommands<'_>;
+    /// Queue an animation to play after the current animation on the track.
+    fn queue_spine(&mut self, animation: &str) -> SpinePlayCommands<'_>;
+    /// Clear a track immediately.
+    fn clear_spine(&mut self, track: usize) -> &mut Self;
+    /// Clear every track immediately.
+    fn clear_spine_tracks(&mut self) -> &mut Self;
+    /// Mix a track out to the setup pose over `mix` seconds.
+    fn stop_spine(&mut self, track: usize, mix: f32) -> &mut Self;
+    /// Set the time of a track and pose the skeleton immediately, see [`SpineSeek`].
+    fn seek_spine(&mut self, seek: SpineSeek) -> &mut Self;
+}
+
+impl SpineEntityCommandsExt for EntityCommands<'_> {
+    fn play_spine(&mut self, animation: &str) -> SpinePlayCommands<'_> {
+        SpinePlayCommands {
+            entity_commands: self.reborrow(),
+            queue: false,
+            play: SpinePlay::new(animation),
+        }
+    }
+
+    fn queue_spine(&mut self, animation: &str) -> SpinePlayCommands<'_> {
+        SpinePlayCommands {
+            entity_commands: self.reborrow(),
+            queue: true,
+            play: SpinePlay::new(animation),
+        }
+    }
+
+    fn clear_spine(&mut self, track: usize) -> &mut Self {
+        self.queue(push_playback(SpinePlayback::Clear(Some(track))))
+    }
+
+    fn clear_spine_tracks(&mut self) -> &mut Self {
+        self.queue(push_playback(SpinePlayback::Clear(None)))
+    }
+
+    fn stop_spine(&mut self, track: usize, mix: f32) -> &mut Self {
+        self.queue(push_playback(SpinePlayback::Stop {
+            track: Some(track),
+            mix,
+        }))
+    }
+
+    fn seek_spine(&mut self, seek: SpineSeek) -> &mut Self {
+        self.queue(push_playback(SpinePlayback::Seek(seek)))
+    }
+}
+
+/// Configures an animation started with [`SpineEntityCommandsExt`]. The request is queued when
+/// this is dropped.
+pub struct SpinePlayCommands<'a> {
+    entity_commands: EntityCommands<'a>,
+    queue: bool,
+    play: SpinePlay,
+}
+
+impl SpinePlayCommands<'_> {
+    pub fn looping(&mut self) -> &mut Self {
+        self.play.looping();
+        self
+    }
+
+    pub fn on_track(&mut self, track: usize) -> &mut Self {
+        self.play.on_track(track);
+        self
+    }
+
+    pub fn mix(&mut self, mix: f32) -> &mut Self {
+        self.play.mix(mix);
+        self
+    }
+
+    pub fn delay(&mut self, delay: f32) -> &mut Self {
+        self.play.delay(delay);
+        self
+    }
+}
+
+impl Drop for SpinePlayCommands<'_> {
+    fn drop(&mut self) {
+        let play = std::mem::take(&mut self.play);
+        let playback = if self.queue {
+            SpinePlayback::Queue(play)
+        } else {
+            SpinePlayback::Play(play)
+        };
+        self.entity_commands.queue(push_playback(playback));
+    }
+}
+
+fn push_playback(playback: SpinePlayback) -> impl FnOnce(EntityWorldMut) + Send + 'static {
+    move |mut entity: EntityWorldMut| {
+        if let Some(mut player) = entity.get_mut::<SpineAnimationPlayer>() {
+            player.push(playback);
+        } else {
+            let mut player = SpineAnimationPlayer::default();
+            player.push(playback);
+            entity.insert(player);
+        }
+    }
+}
+
+pub(crate) fn spine_animation_player(
+    mut player_query: Query<(
+        Entity,
+        &mut Spine,
+        &mut SpineAnimationPlayer,
+        &mut SpineLodState,
+    )>,
+) {
+    for (entity, mut spine, mut player, mut lod_state) in player_query.iter_mut() {
+        if player.pending.is_empty() {
+            continue;
+        }
+        for playback in player.pending.drain(..) {
+            if let SpinePlayback::Seek(seek) = &playback {
+                lod_state.seeked = true;
+                lod_state.suppress_events |= seek.suppress_events;
+            }
+            if let Err(err) = playback.apply(&mut spine.animation_state) {
+                warn!("failed to apply {playback:?} to spine entity {entity}: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{SpineAnimationPlayer, SpineEntityCommandsExt, spine_animation_player};
+    use crate::{lod::SpineLodState, test_support::spineboy};
+
+    #[test]
+    fn requests_wait_for_ready() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world
+            .commands()
+            .entity(entity)
+            .play_spine("run")
+            .looping()
+            .mix(0.1);
+        world.commands().entity(entity).queue_spine("idle");
+        world.flush();
+
+        // not ready yet, so requests are kept
+        world.run_system_once(spine_animation_player).unwrap();
+        let player = world.get::<SpineAnimationPlayer>(entity).unwrap();
+        assert_eq!(player.pending().count(), 2);
+
+        world
+            .entity_mut(entity)
+            .insert((spineboy(), SpineLodState::default()));
+        world.run_system_once(spine_animation_player).unwrap();
+        let player = world.get::<SpineAnimationPlayer>(entity).unwrap();
+        assert_eq!(player.pending().count(), 0);
+        let spine = world.get::<crate::Spine>(entity).unwrap();
+        let track_entry = spine.animation_state.get_current(0).unwrap();
+        assert_eq!(track_entry.animation().name(), "run");
+        assert!(track_entry.looping());
+        assert_eq!(track_entry.mix_duration(), 0.1);
+        assert_eq!(track_entry.next().unwrap().animation().name(), "idle");
+    }
+}
@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{marker::PhantomData, path::Path, sync::Arc};
 
 use bevy::{
     asset::{AssetLoader, LoadContext, io::Reader},
@@ -6,6 +6,7 @@ use bevy::{
     reflect::TypePath,
 };
 use rusty_spine::SpineError;
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,6 +15,45 @@ pub enum SpineLoaderError {
     Io(#[from] std::io::Error),
     #[error("Spine error: {0}")]
     Spine(#[from] SpineError),
+    #[error("Could not parse file: {0}")]
+    Ron(#[from] ron::de::SpannedError),
+}
+
+/// An [`Asset`] loaded from RON files by [`RonAssetLoader`].
+pub(crate) trait RonAsset: Asset + DeserializeOwned {
+    /// The extensions of files containing this asset, for example `spinesm.ron`.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// Loads a [`RonAsset`] from its files.
+#[derive(TypePath)]
+pub(crate) struct RonAssetLoader<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonAssetLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: RonAsset> AssetLoader for RonAssetLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = SpineLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        T::EXTENSIONS
+    }
 }
 
 /// Bevy asset for [`rusty_spine::Atlas`], loaded from `.atlas` files.
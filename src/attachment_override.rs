@@ -0,0 +1,523 @@
+//! Attachments shown in slots in place of the attachments of the skeleton's own atlas.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::CString,
+    ptr::null_mut,
+    sync::Arc,
+};
+
+use bevy::prelude::*;
+use rusty_spine::c::{
+    SP_ATTACHMENT_MESH, SP_ATTACHMENT_REGION, c_void, spAtlas_findRegion, spAtlasPage,
+    spAtlasPage_create, spAtlasPage_dispose, spAtlasRegion, spAtlasRegion_create,
+    spAtlasRegion_dispose, spAttachment, spAttachment_copy, spAttachment_dispose, spMeshAttachment,
+    spMeshAttachment_updateRegion, spRegionAttachment, spRegionAttachment_create,
+    spRegionAttachment_updateRegion, spSkeleton_getAttachmentForSlotName, spSlot_setAttachment,
+};
+
+use crate::{Atlas, Spine, textures::SpineTexture};
+
+/// Show Bevy images, or regions of other atlases, in slots in place of the attachments from the
+/// skeleton's own atlas.
+///
+/// Useful for equipment or user generated content which is not packed into the atlas, such as a
+/// picture uploaded by the player shown in a picture frame on the character, or weapons shipped
+/// in a separate atlas.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::{SpineAtlasAttachment, SpineAttachmentOverride, SpineImageAttachment};
+/// # fn doc(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// let mut attachment_override = SpineAttachmentOverride::default();
+/// attachment_override.insert(
+///     "gun",
+///     SpineImageAttachment {
+///         size: Some(Vec2::new(200., 100.)),
+///         pivot: Vec2::new(0., 0.5),
+///         ..SpineImageAttachment::new(asset_server.load("sword.png"))
+///     },
+/// );
+/// // keep the shape of the setup attachment, with a region from another atlas
+/// attachment_override.insert(
+///     "front-fist",
+///     SpineAtlasAttachment {
+///         copy: Some("front-fist-closed".to_owned()),
+///         ..SpineAtlasAttachment::new(asset_server.load("dlc/gloves.atlas"), "red-fist")
+///     },
+/// );
+/// commands.spawn((SkeletonDataHandle::default(), attachment_override));
+/// # }
+/// ```
+///
+/// An attachment replaces whatever attachment its slot shows, after
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation). Slots keep following
+/// their animations otherwise, so an attachment is hidden whenever its slot has no attachment.
+/// Attachments are shown once their image or atlas is loaded, and removing a slot from this
+/// component restores its setup attachment. Atlases stay loaded for as long as one of their
+/// regions is shown.
+///
+/// Attachments are drawn with the same blend mode and premultiplied alpha setting as the rest of
+/// the skeleton, so images and atlases should be premultiplied if the skeleton's atlas is.
+#[derive(Component, Default, Debug, Clone, Reflect)]
+#[require(SpineAttachmentOverrideState)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct SpineAttachmentOverride {
+    /// Attachments to show, by slot name.
+    pub slots: HashMap<String, SpineAttachmentSource>,
+}
+
+impl SpineAttachmentOverride {
+    pub fn insert(&mut self, slot: &str, source: impl Into<SpineAttachmentSource>) {
+        self.slots.insert(slot.to_owned(), source.into());
+    }
+
+    pub fn remove(&mut self, slot: &str) {
+        self.slots.remove(slot);
+    }
+}
+
+/// Where the attachment shown in a slot comes from, see [`SpineAttachmentOverride`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum SpineAttachmentSource {
+    Image(SpineImageAttachment),
+    Atlas(SpineAtlasAttachment),
+}
+
+impl From<SpineImageAttachment> for SpineAttachmentSource {
+    fn from(image_attachment: SpineImageAttachment) -> Self {
+        Self::Image(image_attachment)
+    }
+}
+
+impl From<SpineAtlasAttachment> for SpineAttachmentSource {
+    fn from(atlas_attachment: SpineAtlasAttachment) -> Self {
+        Self::Atlas(atlas_attachment)
+    }
+}
+
+/// An image shown in a slot, see [`SpineAttachmentOverride`].
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineImageAttachment {
+    pub image: Handle<Image>,
+    /// The region of the image to show in pixels, or `None` for the whole image.
+    pub rect: Option<Rect>,
+    /// The size of the attachment in skeleton units, or `None` for the size of the region in
+    /// pixels.
+    pub size: Option<Vec2>,
+    /// The position of the pivot relative to the slot's bone.
+    pub offset: Vec2,
+    /// The rotation around the pivot relative to the slot's bone, in radians.
+    pub rotation: f32,
+    /// The point of the image placed at [`SpineImageAttachment::offset`], from `(0, 0)` at the
+    /// bottom left to `(1, 1)` at the top right (default: `(0.5, 0.5)`).
+    pub pivot: Vec2,
+}
+
+impl SpineImageAttachment {
+    pub fn new(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            rect: None,
+            size: None,
+            offset: Vec2::ZERO,
+            rotation: 0.,
+            pivot: Vec2::splat(0.5),
+        }
+    }
+}
+
+/// A region of any loaded [`Atlas`] shown in a slot, see [`SpineAttachmentOverride`].
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineAtlasAttachment {
+    pub atlas: Handle<Atlas>,
+    /// The name of the region in the atlas.
+    pub region: String,
+    /// The name of a region or mesh attachment of the slot in the current skin (or the default
+    /// skin) to copy, showing the region with its shape and placement. If `None`, a region
+    /// attachment is created, placed with the fields below.
+    pub copy: Option<String>,
+    /// The size of the attachment in skeleton units, or `None` for the size of the region in
+    /// pixels.
+    pub size: Option<Vec2>,
+    /// The position of the pivot relative to the slot's bone.
+    pub offset: Vec2,
+    /// The rotation around the pivot relative to the slot's bone, in radians.
+    pub rotation: f32,
+    /// The point of the region placed at [`SpineAtlasAttachment::offset`], from `(0, 0)` at the
+    /// bottom left to `(1, 1)` at the top right (default: `(0.5, 0.5)`).
+    pub pivot: Vec2,
+}
+
+impl SpineAtlasAttachment {
+    pub fn new(atlas: Handle<Atlas>, region: &str) -> Self {
+        Self {
+            atlas,
+            region: region.to_owned(),
+            copy: None,
+            size: None,
+            offset: Vec2::ZERO,
+            rotation: 0.,
+            pivot: Vec2::splat(0.5),
+        }
+    }
+}
+
+/// Images shown by [`SpineAttachmentOverride`], by the texture path of their attachment.
+#[derive(Default, Resource)]
+pub(crate) struct SpineImageTextures(HashMap<String, Handle<Image>>);
+
+impl SpineImageTextures {
+    pub(crate) fn get(&self, texture_path: &str) -> Option<Handle<Image>> {
+        self.0.get(texture_path).cloned()
+    }
+}
+
+/// An atlas region and page created for an image.
+struct ImageRegion {
+    region: *mut spAtlasRegion,
+    page: *mut spAtlasPage,
+    texture_path: String,
+}
+
+impl ImageRegion {
+    fn new(image_attachment: &SpineImageAttachment, image_size: Vec2) -> Self {
+        let texture_path = format!("bevy_spine/image/{:?}", image_attachment.image.id());
+        let rect = image_attachment
+            .rect
+            .unwrap_or(Rect::from_corners(Vec2::ZERO, image_size));
+        let name = CString::new(texture_path.as_str()).unwrap_or_default();
+        unsafe {
+            let page = spAtlasPage_create(null_mut(), name.as_ptr());
+            (*page).width = image_size.x as i32;
+            (*page).height = image_size.y as i32;
+            // disposed by the dispose texture callback, see `SpineTextures`
+            (*page).rendererObject =
+                Box::into_raw(Box::new(SpineTexture(texture_path.clone()))) as *mut c_void;
+
+            let region = spAtlasRegion_create();
+            (*region).page = page;
+            let texture_region = &mut (*region).super_0;
+            texture_region.rendererObject = page as *mut c_void;
+            texture_region.u = rect.min.x / image_size.x;
+            texture_region.v = rect.min.y / image_size.y;
+            texture_region.u2 = rect.max.x / image_size.x;
+            texture_region.v2 = rect.max.y / image_size.y;
+            texture_region.width = rect.width() as i32;
+            texture_region.height = rect.height() as i32;
+            texture_region.originalWidth = rect.width() as i32;
+            texture_region.originalHeight = rect.height() as i32;
+
+            Self {
+                region,
+                page,
+                texture_path,
+            }
+        }
+    }
+}
+
+impl Drop for ImageRegion {
+    fn drop(&mut self) {
+        unsafe {
+            spAtlasRegion_dispose(self.region);
+            spAtlasPage_dispose(self.page);
+        }
+    }
+}
+
+/// An attachment created for a [`SpineAttachmentSource`].
+struct OverrideAttachment {
+    source: SpineAttachmentSource,
+    attachment: *mut spAttachment,
+    image_region: Option<ImageRegion>,
+    /// Keeps the regions of the atlas alive, even if the asset is reloaded.
+    _atlas: Option<Arc<rusty_spine::Atlas>>,
+}
+
+// the pointers are owned by this attachment and only used by the skeleton it is shown on
+unsafe impl Send for OverrideAttachment {}
+unsafe impl Sync for OverrideAttachment {}
+
+impl OverrideAttachment {
+    fn new(
+        spine: &Spine,
+        slot: &str,
+        source: &SpineAttachmentSource,
+        images: &Assets<Image>,
+        atlases: &Assets<Atlas>,
+    ) -> Result<Option<Self>, String> {
+        match source {
+            SpineAttachmentSource::Image(image_attachment) => {
+                let Some(image) = images.get(&image_attachment.image) else {
+                    return Ok(None);
+                };
+                let image_region = ImageRegion::new(image_attachment, image.size_f32());
+                let attachment = unsafe {
+                    region_attachment(
+                        &image_region.texture_path,
+                        image_region.region,
+                        image_attachment.size,
+                        image_attachment.offset,
+                        image_attachment.rotation,
+                        image_attachment.pivot,
+                    )
+                };
+                Ok(Some(Self {
+                    source: source.clone(),
+                    attachment,
+                    image_region: Some(image_region),
+                    _atlas: None,
+                }))
+            }
+            SpineAttachmentSource::Atlas(atlas_attachment) => {
+                let Some(atlas) = atlases.get(&atlas_attachment.atlas) else {
+                    return Ok(None);
+                };
+                let region_name =
+                    CString::new(atlas_attachment.region.as_str()).unwrap_or_default();
+                let region =
+                    unsafe { spAtlas_findRegion(atlas.atlas.c_ptr(), region_name.as_ptr()) };
+                if region.is_null() {
+                    return Err(format!(
+                        "unknown atlas region {:?}",
+                        atlas_attachment.region
+                    ));
+                }
+                let attachment = match &atlas_attachment.copy {
+                    Some(copy) => unsafe { copy_attachment(spine, slot, copy, region)? },
+                    None => unsafe {
+                        region_attachment(
+                            &atlas_attachment.region,
+                            region,
+                            atlas_attachment.size,
+                            atlas_attachment.offset,
+                            atlas_attachment.rotation,
+                            atlas_attachment.pivot,
+                        )
+                    },
+                };
+                Ok(Some(Self {
+                    source: source.clone(),
+                    attachment,
+                    image_region: None,
+                    _atlas: Some(atlas.atlas.clone()),
+                }))
+            }
+        }
+    }
+}
+
+impl Drop for OverrideAttachment {
+    fn drop(&mut self) {
+        // the region is disposed after the attachment, see `ImageRegion`
+        unsafe {
+            spAttachment_dispose(self.attachment);
+        }
+    }
+}
+
+/// Create a region attachment showing a region, centered on its pivot.
+unsafe fn region_attachment(
+    name: &str,
+    region: *mut spAtlasRegion,
+    size: Option<Vec2>,
+    offset: Vec2,
+    rotation: f32,
+    pivot: Vec2,
+) -> *mut spAttachment {
+    unsafe {
+        let texture_region = &mut (*region).super_0;
+        let size = size.unwrap_or(Vec2::new(
+            texture_region.originalWidth as f32,
+            texture_region.originalHeight as f32,
+        ));
+        // region attachments are centered on their position
+        let center = offset + Vec2::from_angle(rotation).rotate((Vec2::splat(0.5) - pivot) * size);
+        let name = CString::new(name).unwrap_or_default();
+        let attachment = spRegionAttachment_create(name.as_ptr());
+        (*attachment).x = center.x;
+        (*attachment).y = center.y;
+        (*attachment).rotation = rotation.to_degrees();
+        (*attachment).width = size.x;
+        (*attachment).height = size.y;
+        (*attachment).rendererObject = region as *mut c_void;
+        (*attachment).region = texture_region;
+        spRegionAttachment_updateRegion(attachment);
+        attachment as *mut spAttachment
+    }
+}
+
+/// Copy a region or mesh attachment of a slot, showing another region instead.
+unsafe fn copy_attachment(
+    spine: &Spine,
+    slot: &str,
+    attachment: &str,
+    region: *mut spAtlasRegion,
+) -> Result<*mut spAttachment, String> {
+    let slot_name = CString::new(slot).unwrap_or_default();
+    let attachment_name = CString::new(attachment).unwrap_or_default();
+    unsafe {
+        let source = spSkeleton_getAttachmentForSlotName(
+            spine.skeleton.c_ptr(),
+            slot_name.as_ptr(),
+            attachment_name.as_ptr(),
+        );
+        if source.is_null() {
+            return Err(format!("unknown attachment {attachment:?} to copy"));
+        }
+        match (*source).type_0 {
+            SP_ATTACHMENT_REGION => {
+                let copy = spAttachment_copy(source) as *mut spRegionAttachment;
+                (*copy).rendererObject = region as *mut c_void;
+                (*copy).region = &mut (*region).super_0;
+                spRegionAttachment_updateRegion(copy);
+                Ok(copy as *mut spAttachment)
+            }
+            SP_ATTACHMENT_MESH => {
+                let copy = spAttachment_copy(source) as *mut spMeshAttachment;
+                (*copy).rendererObject = region as *mut c_void;
+                (*copy).region = &mut (*region).super_0;
+                spMeshAttachment_updateRegion(copy);
+                Ok(copy as *mut spAttachment)
+            }
+            _ => Err(format!(
+                "attachment {attachment:?} cannot be copied, only region and mesh attachments can"
+            )),
+        }
+    }
+}
+
+/// The attachments created for a [`SpineAttachmentOverride`], by slot name.
+#[derive(Component, Default)]
+pub(crate) struct SpineAttachmentOverrideState {
+    attachments: HashMap<String, OverrideAttachment>,
+    /// Sources which could not be shown, so they are not retried until they change.
+    failed: HashMap<String, SpineAttachmentSource>,
+}
+
+/// Show an attachment in a slot, unless the slot has no attachment. Returns false if there is no
+/// such slot.
+fn show(spine: &mut Spine, slot: &str, override_attachment: &OverrideAttachment) -> bool {
+    let Some(slot) = spine.skeleton.find_slot_mut(slot) else {
+        return false;
+    };
+    if slot
+        .attachment()
+        .is_some_and(|current| current.c_ptr() != override_attachment.attachment)
+    {
+        unsafe {
+            spSlot_setAttachment(slot.c_ptr(), override_attachment.attachment);
+        }
+    }
+    true
+}
+
+/// Restore the setup attachment of a slot, if it shows an attachment about to be disposed.
+fn hide(spine: &mut Spine, slot: &str, override_attachment: &OverrideAttachment) {
+    let setup_attachment = {
+        let Some(slot) = spine.skeleton.find_slot(slot) else {
+            return;
+        };
+        if slot.attachment().map(|attachment| attachment.c_ptr())
+            != Some(override_attachment.attachment)
+        {
+            return;
+        }
+        slot.data().attachment_name().map(str::to_owned)
+    };
+    spine
+        .skeleton
+        .set_attachment(slot, setup_attachment.as_deref());
+}
+
+pub(crate) fn spine_attachment_overrides(
+    mut spine_query: Query<(
+        Entity,
+        &mut Spine,
+        Option<Ref<SpineAttachmentOverride>>,
+        &mut SpineAttachmentOverrideState,
+    )>,
+    mut removed_states: RemovedComponents<SpineAttachmentOverrideState>,
+    mut image_textures: ResMut<SpineImageTextures>,
+    images: Res<Assets<Image>>,
+    atlases: Res<Assets<Atlas>>,
+) {
+    let mut disposed = removed_states.read().count() > 0;
+    let mut texture_paths = HashSet::new();
+    for (entity, mut spine, attachment_override, mut state) in spine_query.iter_mut() {
+        let state = state.as_mut();
+        if attachment_override
+            .as_ref()
+            .is_none_or(|attachment_override| attachment_override.is_changed())
+        {
+            state.attachments.retain(|slot, override_attachment| {
+                let keep = attachment_override
+                    .as_ref()
+                    .and_then(|attachment_override| attachment_override.slots.get(slot))
+                    .is_some_and(|source| *source == override_attachment.source);
+                if !keep {
+                    hide(&mut spine, slot, override_attachment);
+                    disposed = true;
+                }
+                keep
+            });
+        }
+        if let Some(attachment_override) = attachment_override {
+            for (slot, source) in attachment_override.slots.iter() {
+                if !state.attachments.contains_key(slot) {
+                    if state.failed.get(slot) == Some(source) {
+                        continue;
+                    }
+                    match OverrideAttachment::new(&spine, slot, source, &images, &atlases) {
+                        Ok(Some(override_attachment)) => {
+                            if let (Some(image_region), SpineAttachmentSource::Image(image)) =
+                                (&override_attachment.image_region, source)
+                            {
+                                image_textures
+                                    .0
+                                    .insert(image_region.texture_path.clone(), image.image.clone());
+                            }
+                            state.attachments.insert(slot.clone(), override_attachment);
+                        }
+                        // still loading
+                        Ok(None) => continue,
+                        Err(err) => {
+                            warn!(
+                                "cannot override slot {slot:?} of {entity} with \
+                                SpineAttachmentOverride: {err}"
+                            );
+                            state.failed.insert(slot.clone(), source.clone());
+                            continue;
+                        }
+                    }
+                }
+                if !show(&mut spine, slot, &state.attachments[slot])
+                    && attachment_override.is_changed()
+                {
+                    warn!("unknown spine slot {slot:?} in SpineAttachmentOverride of {entity}");
+                }
+            }
+        }
+        texture_paths.extend(
+            state
+                .attachments
+                .values()
+                .filter_map(|override_attachment| {
+                    override_attachment
+                        .image_region
+                        .as_ref()
+                        .map(|image_region| image_region.texture_path.clone())
+                }),
+        );
+    }
+    if disposed {
+        image_textures
+            .0
+            .retain(|texture_path, _| texture_paths.contains(texture_path));
+    }
+}
@@ -0,0 +1,216 @@
+//! Notifications for slot attachments changing during animations.
+
+use bevy::prelude::*;
+
+use crate::{Spine, SpineName};
+
+/// Send [`SpineAttachmentChanged`] whenever a slot of this Spine shows a different attachment.
+///
+/// Attachments are compared after every [`SpineSystem::UpdateAnimation`], so only Spine entities
+/// with this component pay for it. To limit it further, list the slots to watch:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::{SpineAttachmentChanged, SpineAttachmentWatch};
+/// # fn doc(mut commands: Commands) {
+/// commands
+///     .spawn((
+///         SkeletonDataHandle::default(),
+///         SpineAttachmentWatch::new(["muzzle", "eye"]),
+///     ))
+///     .observe(|event: On<SpineAttachmentChanged>| {
+///         if event.slot == "muzzle" && event.new.is_some() {
+///             println!("muzzle flash on {}", event.original_event_target());
+///         }
+///     });
+/// # }
+/// ```
+///
+/// Changes are compared by attachment name, so switching to a skin with an attachment of the same
+/// name is not reported.
+///
+/// [`SpineSystem::UpdateAnimation`]: crate::SpineSystem::UpdateAnimation
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq, Reflect)]
+#[require(SpineAttachmentWatchState)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineAttachmentWatch {
+    /// Slots to watch, by name. Every slot is watched if empty.
+    pub slots: Vec<String>,
+}
+
+impl SpineAttachmentWatch {
+    pub fn new<I, S>(slots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            slots: slots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Sent, and triggered on the Spine entity, when a slot watched by [`SpineAttachmentWatch`] shows
+/// a different attachment.
+///
+/// Like every Spine [`EntityEvent`], this propagates up the hierarchy through [`ChildOf`]. While
+/// propagating, `entity` is the entity currently observing, and [`On::original_event_target`] is
+/// the Spine entity.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::SpineAttachmentChanged;
+/// fn on_attachment_changed(mut attachment_events: MessageReader<SpineAttachmentChanged>) {
+///     for event in attachment_events.read() {
+///         println!(
+///             "{} on {}: {:?} -> {:?}",
+///             event.slot, event.entity, event.old, event.new
+///         );
+///     }
+/// }
+/// ```
+#[derive(EntityEvent, Message, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAttachmentChanged {
+    pub entity: Entity,
+    pub slot: SpineName,
+    /// The previous attachment name, or `None` if the slot was empty.
+    pub old: Option<SpineName>,
+    /// The new attachment name, or `None` if the slot is now empty.
+    pub new: Option<SpineName>,
+}
+
+#[derive(Component, Default)]
+pub(crate) struct SpineAttachmentWatchState {
+    slots: Vec<WatchedSlot>,
+}
+
+struct WatchedSlot {
+    index: usize,
+    slot: SpineName,
+    /// The address of the attachment, to skip comparing names while it stays the same.
+    attachment_address: usize,
+    attachment: Option<SpineName>,
+}
+
+pub(crate) fn spine_attachment_watch(
+    mut commands: Commands,
+    mut spine_query: Query<(
+        Entity,
+        Ref<Spine>,
+        Ref<SpineAttachmentWatch>,
+        &mut SpineAttachmentWatchState,
+    )>,
+    mut attachment_events: MessageWriter<SpineAttachmentChanged>,
+) {
+    for (entity, spine, watch, mut state) in spine_query.iter_mut() {
+        let skeleton = &spine.skeleton;
+        if spine.is_added() || watch.is_changed() {
+            for name in watch.slots.iter() {
+                if skeleton.find_slot(name).is_none() {
+                    warn!("cannot watch unknown spine slot: {name}");
+                }
+            }
+            state.slots = skeleton
+                .slots()
+                .enumerate()
+                .filter(|(_, slot)| {
+                    watch.slots.is_empty()
+                        || watch.slots.iter().any(|name| name == slot.data().name())
+                })
+                .map(|(index, slot)| {
+                    let attachment = slot.attachment();
+                    WatchedSlot {
+                        index,
+                        slot: SpineName::from(slot.data().name()),
+                        attachment_address: attachment
+                            .as_ref()
+                            .map_or(0, |attachment| attachment.c_ptr() as usize),
+                        attachment: attachment
+                            .as_ref()
+                            .map(|attachment| SpineName::from(attachment.name())),
+                    }
+                })
+                .collect();
+            continue;
+        }
+        for watched_slot in state.slots.iter_mut() {
+            let Some(slot) = skeleton.slot_at_index(watched_slot.index) else {
+                continue;
+            };
+            let attachment = slot.attachment();
+            let attachment_address = attachment
+                .as_ref()
+                .map_or(0, |attachment| attachment.c_ptr() as usize);
+            if attachment_address == watched_slot.attachment_address {
+                continue;
+            }
+            watched_slot.attachment_address = attachment_address;
+            let name = attachment.as_ref().map(|attachment| attachment.name());
+            if watched_slot.attachment.as_deref() == name {
+                continue;
+            }
+            let new = name.map(SpineName::from);
+            let event = SpineAttachmentChanged {
+                entity,
+                slot: watched_slot.slot.clone(),
+                old: std::mem::replace(&mut watched_slot.attachment, new.clone()),
+                new,
+            };
+            commands.trigger(event.clone());
+            attachment_events.write(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use rusty_spine::Physics;
+
+    use super::{SpineAttachmentChanged, SpineAttachmentWatch, spine_attachment_watch};
+    use crate::{Spine, test_support::spineboy};
+
+    fn play(world: &mut World, entity: Entity, animation: &str) {
+        let mut spine = world.get_mut::<Spine>(entity).unwrap();
+        spine
+            .animation_state
+            .set_animation_by_name(0, animation, true)
+            .unwrap();
+        spine.update(0.1, Physics::Update);
+    }
+
+    #[test]
+    fn watches_listed_slots() {
+        let mut world = World::new();
+        world.init_resource::<Messages<SpineAttachmentChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(spine_attachment_watch);
+        let entity = world
+            .spawn((spineboy(), SpineAttachmentWatch::new(["front-fist"])))
+            .id();
+        play(&mut world, entity, "walk");
+        schedule.run(&mut world);
+
+        // death also changes the eye and the mouth, which are not watched
+        play(&mut world, entity, "death");
+        schedule.run(&mut world);
+        let changes: Vec<_> = world
+            .resource_mut::<Messages<SpineAttachmentChanged>>()
+            .drain()
+            .map(|event| {
+                assert_eq!(event.entity, entity);
+                (event.slot.to_string(), event.old, event.new)
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            [(
+                "front-fist".to_owned(),
+                Some("front-fist-closed".into()),
+                Some("front-fist-open".into())
+            )]
+        );
+    }
+}
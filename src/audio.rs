@@ -0,0 +1,259 @@
+//! Built-in playback of Spine audio events, enabled with the `audio` feature.
+
+use bevy::{
+    audio::{AudioSink, AudioSinkPlayback, SpatialScale, Volume},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{SkeletonData, SkeletonDataHandle, SkeletonDataStatus, SpineEvent, SpineSet};
+
+/// Plays the audio keyed on Spine events (see [`SpineEvent::Event`]) with `bevy_audio`.
+///
+/// Added automatically by [`SpinePlugin`](crate::SpinePlugin) when the `audio` feature is enabled.
+/// Configure it with the [`SpineAudioSettings`] resource and the per-entity [`SpineAudio`]
+/// component. Audio formats must be enabled on Bevy separately (for example the `vorbis` or `wav`
+/// features).
+pub struct SpineAudioPlugin;
+
+impl Plugin for SpineAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpineAudioSettings>()
+            .register_type::<SpineAudio>()
+            .init_resource::<SpineAudioSettings>()
+            .init_resource::<SpineAudioClips>()
+            .add_systems(
+                Update,
+                (
+                    spine_audio_preload,
+                    spine_audio_play.in_set(SpineSet::OnEvent),
+                    spine_audio_update_sinks,
+                ),
+            );
+    }
+}
+
+/// Global settings for Spine audio playback.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct SpineAudioSettings {
+    /// The directory, relative to the Bevy asset folder, that event audio paths are relative to.
+    ///
+    /// This should match the audio folder set in the Spine editor (default: `""`).
+    pub base_path: String,
+    /// A volume multiplier applied to all Spine audio (default: `1.0`).
+    pub volume: f32,
+    /// If true, every audio clip referenced by a [`SkeletonData`] is loaded as soon as the
+    /// skeleton is, so that the first event plays without delay (default: `true`).
+    pub preload: bool,
+}
+
+impl Default for SpineAudioSettings {
+    fn default() -> Self {
+        Self {
+            base_path: String::new(),
+            volume: 1.,
+            preload: true,
+        }
+    }
+}
+
+impl SpineAudioSettings {
+    /// The asset path of an audio path set on a Spine event.
+    pub fn clip_path(&self, audio_path: &str) -> String {
+        let base_path = self.base_path.trim_end_matches('/');
+        if base_path.is_empty() {
+            audio_path.to_owned()
+        } else {
+            format!("{base_path}/{audio_path}")
+        }
+    }
+}
+
+/// Controls the audio played by Spine events on this entity.
+///
+/// Entities without this component play their audio at full volume, panned by the event balance.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineAudio {
+///         spatial: true,
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+///
+/// fn duck(mut spine_audio_query: Query<&mut SpineAudio>) {
+///     for mut spine_audio in spine_audio_query.iter_mut() {
+///         spine_audio.volume = 0.25;
+///     }
+/// }
+/// ```
+///
+/// Changes to `muted` and `volume` also apply to sounds which are already playing.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineAudio {
+    pub muted: bool,
+    /// A volume multiplier, useful for ducking (default: `1.0`).
+    pub volume: f32,
+    /// If true, sounds are played spatially from this entity, and the event balance is ignored.
+    /// Requires a [`SpatialListener`] (default: `false`).
+    pub spatial: bool,
+}
+
+impl Default for SpineAudio {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            volume: 1.,
+            spatial: false,
+        }
+    }
+}
+
+/// Audio clips kept loaded for Spine events, by asset path.
+#[derive(Resource, Default)]
+struct SpineAudioClips(HashMap<String, Handle<AudioSource>>);
+
+impl SpineAudioClips {
+    fn get(&mut self, path: String, asset_server: &AssetServer) -> Handle<AudioSource> {
+        self.0
+            .entry(path)
+            .or_insert_with_key(|path| asset_server.load(path.clone()))
+            .clone()
+    }
+}
+
+/// A sound played by a Spine event.
+#[derive(Component)]
+struct SpineAudioEmitter {
+    spine: Entity,
+    /// The volume of the event, before any [`SpineAudio`] or [`SpineAudioSettings`] multiplier.
+    volume: f32,
+}
+
+fn spine_audio_preload(
+    mut skeleton_data_events: MessageReader<AssetEvent<SkeletonData>>,
+    mut clips: ResMut<SpineAudioClips>,
+    skeleton_data_assets: Res<Assets<SkeletonData>>,
+    settings: Res<SpineAudioSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in skeleton_data_events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if !settings.preload {
+            continue;
+        }
+        let Some(SkeletonData {
+            status: SkeletonDataStatus::Loaded(skeleton_data),
+            ..
+        }) = skeleton_data_assets.get(*id)
+        else {
+            continue;
+        };
+        for event_data in skeleton_data.events() {
+            let audio_path = event_data.audio_path();
+            if !audio_path.is_empty() {
+                clips.get(settings.clip_path(audio_path), &asset_server);
+            }
+        }
+    }
+}
+
+fn spine_audio_play(
+    mut commands: Commands,
+    mut spine_events: MessageReader<SpineEvent>,
+    mut clips: ResMut<SpineAudioClips>,
+    spine_query: Query<Option<&SpineAudio>, With<SkeletonDataHandle>>,
+    listener_query: Query<(&SpatialListener, &GlobalTransform)>,
+    settings: Res<SpineAudioSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in spine_events.read() {
+        let SpineEvent::Event {
+            entity,
+            audio_path,
+            volume,
+            balance,
+            ..
+        } = event
+        else {
+            continue;
+        };
+        if audio_path.is_empty() {
+            continue;
+        }
+        let Ok(spine_audio) = spine_query.get(*entity) else {
+            continue;
+        };
+        let spine_audio = spine_audio.cloned().unwrap_or_default();
+        let clip = clips.get(settings.clip_path(audio_path), &asset_server);
+        let emitter = SpineAudioEmitter {
+            spine: *entity,
+            volume: *volume,
+        };
+        let playback_settings = PlaybackSettings::DESPAWN.with_volume(Volume::Linear(
+            volume * spine_audio.volume * settings.volume,
+        ));
+        let playback_settings = PlaybackSettings {
+            muted: spine_audio.muted,
+            ..playback_settings
+        };
+        if spine_audio.spatial {
+            commands.entity(*entity).with_child((
+                AudioPlayer(clip),
+                playback_settings.with_spatial(true),
+                emitter,
+                Transform::default(),
+            ));
+        } else if let Some((listener, listener_transform)) = listener_query.iter().next()
+            && *balance != 0.
+        {
+            // place the sound on the axis between the ears, scaled so that the ears are one unit
+            // apart, which pans the sound without attenuating it by distance
+            let center = listener.left_ear_offset.midpoint(listener.right_ear_offset);
+            let offset = center.lerp(listener.right_ear_offset, balance.clamp(-1., 1.));
+            let ear_distance = listener.left_ear_offset.distance(listener.right_ear_offset);
+            commands.spawn((
+                AudioPlayer(clip),
+                playback_settings
+                    .with_spatial(true)
+                    .with_spatial_scale(SpatialScale::new(1. / ear_distance.max(f32::EPSILON))),
+                Transform::from_translation(listener_transform.transform_point(offset)),
+                emitter,
+            ));
+        } else {
+            commands.spawn((AudioPlayer(clip), playback_settings, emitter));
+        }
+    }
+}
+
+fn spine_audio_update_sinks(
+    mut sink_query: Query<(&SpineAudioEmitter, &mut AudioSink)>,
+    spine_audio_query: Query<Ref<SpineAudio>>,
+    settings: Res<SpineAudioSettings>,
+) {
+    for (emitter, mut sink) in sink_query.iter_mut() {
+        let Ok(spine_audio) = spine_audio_query.get(emitter.spine) else {
+            continue;
+        };
+        if !settings.is_changed() && !spine_audio.is_changed() {
+            continue;
+        }
+        sink.set_volume(Volume::Linear(
+            emitter.volume * spine_audio.volume * settings.volume,
+        ));
+        if spine_audio.muted {
+            sink.mute();
+        } else {
+            sink.unmute();
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Parameter driven blending between several animations.
+
+use bevy::prelude::*;
+
+use crate::Spine;
+
+/// Blends between several looping animations based on one or two parameters, for example walk,
+/// run and sprint driven by a speed, or an 8-way aim driven by a direction.
+///
+/// Each animation plays on its own track, starting at [`SpineBlendSpace::first_track`]. Every
+/// frame before [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation), track
+/// alphas are set so that the tracks blend by their weights, and track time scales are set so that
+/// all cycles stay phase-aligned (for example, both feet land at the same time in walk and run).
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineBlendSpace::new_1d(0, [("idle", 0.), ("walk", 0.5), ("run", 1.)]),
+/// ));
+/// # }
+///
+/// fn set_speed(mut blend_space_query: Query<&mut SpineBlendSpace>) {
+///     for mut blend_space in blend_space_query.iter_mut() {
+///         blend_space.set_parameter_1d(0.75);
+///     }
+/// }
+/// ```
+///
+/// User events fire from every track in the blend space, including tracks with no weight.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct SpineBlendSpace {
+    /// The track of the first animation. Each further animation uses the next track.
+    pub first_track: usize,
+    pub kind: SpineBlendSpaceKind,
+    pub points: Vec<SpineBlendPoint>,
+    /// The current blend parameter. For [`SpineBlendSpaceKind::OneD`], only `x` is used.
+    pub parameter: Vec2,
+    /// If true, time scales are adjusted to keep all animations phase-aligned (default: `true`).
+    pub sync: bool,
+}
+
+/// See [`SpineBlendSpace::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum SpineBlendSpaceKind {
+    /// Blends linearly between the two points surrounding the parameter.
+    OneD,
+    /// Blends between the points surrounding the parameter, using gradient band interpolation.
+    ///
+    /// Each point's weight falls off linearly towards every other point, so the parameter between
+    /// two neighbours only blends those two (for example, up and up-right in an 8-way aim), and
+    /// the parameter on a point plays that point's animation alone.
+    TwoD,
+}
+
+/// An animation positioned in a [`SpineBlendSpace`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct SpineBlendPoint {
+    pub animation: String,
+    pub position: Vec2,
+}
+
+impl SpineBlendSpace {
+    /// A blend space driven by a single parameter.
+    pub fn new_1d<'a>(
+        first_track: usize,
+        points: impl IntoIterator<Item = (&'a str, f32)>,
+    ) -> Self {
+        Self {
+            first_track,
+            kind: SpineBlendSpaceKind::OneD,
+            points: points
+                .into_iter()
+                .map(|(animation, position)| SpineBlendPoint {
+                    animation: animation.to_owned(),
+                    position: Vec2::new(position, 0.),
+                })
+                .collect(),
+            parameter: Vec2::ZERO,
+            sync: true,
+        }
+    }
+
+    /// A blend space driven by two parameters.
+    pub fn new_2d<'a>(
+        first_track: usize,
+        points: impl IntoIterator<Item = (&'a str, Vec2)>,
+    ) -> Self {
+        Self {
+            first_track,
+            kind: SpineBlendSpaceKind::TwoD,
+            points: points
+                .into_iter()
+                .map(|(animation, position)| SpineBlendPoint {
+                    animation: animation.to_owned(),
+                    position,
+                })
+                .collect(),
+            parameter: Vec2::ZERO,
+            sync: true,
+        }
+    }
+
+    pub fn set_parameter_1d(&mut self, parameter: f32) {
+        self.parameter = Vec2::new(parameter, 0.);
+    }
+
+    pub fn set_parameter_2d(&mut self, parameter: Vec2) {
+        self.parameter = parameter;
+    }
+
+    /// The normalized weight of each point for the current parameter.
+    pub fn weights(&self) -> Vec<f32> {
+        let mut weights = vec![0.; self.points.len()];
+        match self.kind {
+            SpineBlendSpaceKind::OneD => {
+                let x = self.parameter.x;
+                let mut below: Option<(usize, f32)> = None;
+                let mut above: Option<(usize, f32)> = None;
+                for (index, point) in self.points.iter().enumerate() {
+                    let position = point.position.x;
+                    if position <= x && below.is_none_or(|(_, below)| position > below) {
+                        below = Some((index, position));
+                    }
+                    if position >= x && above.is_none_or(|(_, above)| position < above) {
+                        above = Some((index, position));
+                    }
+                }
+                match (below, above) {
+                    (Some((below, below_x)), Some((above, above_x))) if below != above => {
+                        let t = (x - below_x) / (above_x - below_x);
+                        weights[below] = 1. - t;
+                        weights[above] = t;
+                    }
+                    (Some((index, _)), _) | (None, Some((index, _))) => {
+                        weights[index] = 1.;
+                    }
+                    (None, None) => {}
+                }
+            }
+            SpineBlendSpaceKind::TwoD => {
+                for (index, (weight, point)) in
+                    weights.iter_mut().zip(self.points.iter()).enumerate()
+                {
+                    let to_parameter = self.parameter - point.position;
+                    *weight = self
+                        .points
+                        .iter()
+                        .enumerate()
+                        .filter(|(other_index, _)| *other_index != index)
+                        .filter_map(|(_, other)| {
+                            let to_other = other.position - point.position;
+                            let length_squared = to_other.length_squared();
+                            (length_squared > 0.).then(|| {
+                                (1. - to_parameter.dot(to_other) / length_squared).clamp(0., 1.)
+                            })
+                        })
+                        .fold(1., f32::min);
+                }
+                let total: f32 = weights.iter().sum();
+                if total > 0. {
+                    for weight in weights.iter_mut() {
+                        *weight /= total;
+                    }
+                }
+            }
+        }
+        weights
+    }
+}
+
+pub(crate) fn spine_blend_space(mut blend_space_query: Query<(&mut Spine, &SpineBlendSpace)>) {
+    for (mut spine, blend_space) in blend_space_query.iter_mut() {
+        let animation_state = &mut spine.animation_state;
+
+        let playing = blend_space.points.iter().enumerate().all(|(index, point)| {
+            animation_state
+                .track_at_index(blend_space.first_track + index)
+                .is_some_and(|track| track.animation().name() == point.animation)
+        });
+        if !playing {
+            for (index, point) in blend_space.points.iter().enumerate() {
+                if let Err(err) = animation_state.set_animation_by_name(
+                    blend_space.first_track + index,
+                    &point.animation,
+                    true,
+                ) {
+                    warn!(
+                        "spine blend space failed to play {:?}: {err}",
+                        point.animation
+                    );
+                }
+            }
+        }
+
+        let weights = blend_space.weights();
+        let mut blended_duration = 0.;
+        for (index, weight) in weights.iter().enumerate() {
+            if let Some(track) = animation_state.track_at_index(blend_space.first_track + index) {
+                blended_duration += track.animation().duration() * weight;
+            }
+        }
+
+        // tracks are applied in order, so each track mixes over the result of all previous tracks
+        let mut accumulated_weight = 0.;
+        for (index, weight) in weights.iter().enumerate() {
+            accumulated_weight += weight;
+            let Some(mut track) =
+                animation_state.track_at_index_mut(blend_space.first_track + index)
+            else {
+                continue;
+            };
+            track.set_alpha(if accumulated_weight > 0. {
+                weight / accumulated_weight
+            } else {
+                0.
+            });
+            if blend_space.sync && blended_duration > 0. {
+                let duration = track.animation().duration();
+                track.set_timescale(duration / blended_duration);
+            } else {
+                track.set_timescale(1.);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use bevy::prelude::*;
+
+    use super::SpineBlendSpace;
+
+    fn assert_weights(weights: &[f32], expected: &[f32]) {
+        assert_eq!(weights.len(), expected.len());
+        for (weight, expected) in weights.iter().zip(expected.iter()) {
+            assert!(
+                (weight - expected).abs() < 1e-4,
+                "{weights:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn weights_1d() {
+        let mut blend_space =
+            SpineBlendSpace::new_1d(0, [("idle", 0.), ("walk", 0.5), ("run", 1.)]);
+        blend_space.set_parameter_1d(0.75);
+        assert_weights(&blend_space.weights(), &[0., 0.5, 0.5]);
+        blend_space.set_parameter_1d(0.5);
+        assert_weights(&blend_space.weights(), &[0., 1., 0.]);
+        blend_space.set_parameter_1d(-1.);
+        assert_weights(&blend_space.weights(), &[1., 0., 0.]);
+        blend_space.set_parameter_1d(2.);
+        assert_weights(&blend_space.weights(), &[0., 0., 1.]);
+    }
+
+    fn aim_8_way() -> SpineBlendSpace {
+        let names = ["e", "ne", "n", "nw", "w", "sw", "s", "se"];
+        SpineBlendSpace::new_2d(
+            0,
+            names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| (*name, Vec2::from_angle(index as f32 * FRAC_PI_4))),
+        )
+    }
+
+    #[test]
+    fn weights_2d_on_points() {
+        let mut blend_space = aim_8_way();
+        for index in 0..8 {
+            blend_space.set_parameter_2d(blend_space.points[index].position);
+            let mut expected = [0.; 8];
+            expected[index] = 1.;
+            assert_weights(&blend_space.weights(), &expected);
+        }
+    }
+
+    #[test]
+    fn weights_2d_between_neighbours() {
+        let mut blend_space = aim_8_way();
+        let (east, north_east) = (
+            blend_space.points[0].position,
+            blend_space.points[1].position,
+        );
+        blend_space.set_parameter_2d((east + north_east) / 2.);
+        assert_weights(&blend_space.weights(), &[0.5, 0.5, 0., 0., 0., 0., 0., 0.]);
+        blend_space.set_parameter_2d(east.lerp(north_east, 0.25));
+        assert_weights(
+            &blend_space.weights(),
+            &[0.75, 0.25, 0., 0., 0., 0., 0., 0.],
+        );
+    }
+
+    #[test]
+    fn weights_2d_normalized() {
+        let mut blend_space = aim_8_way();
+        for parameter in [Vec2::ZERO, Vec2::new(0.3, -0.2), Vec2::new(2., 1.)] {
+            blend_space.set_parameter_2d(parameter);
+            let weights = blend_space.weights();
+            assert!(
+                (weights.iter().sum::<f32>() - 1.).abs() < 1e-4,
+                "{weights:?}"
+            );
+            assert!(weights.iter().all(|weight| *weight >= 0.), "{weights:?}");
+        }
+    }
+}
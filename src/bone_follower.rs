@@ -0,0 +1,173 @@
+//! Entities following a single bone, without spawning bone entities.
+
+use bevy::prelude::*;
+use rusty_spine::BoneHandle;
+
+use crate::{Spine, bone_world_transform};
+
+/// Moves this entity along with a bone of a [`Spine`], for example a sword held in a hand or a
+/// particle emitter at the tip of a gun.
+///
+/// The bone's world transform is read directly from the skeleton after every
+/// [`SpineSystem::UpdateAnimation`] (and after [`SpineSync`]), so neither bone entities (see
+/// [`SpineLoader::without_children`]) nor synchronization are needed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineBoneFollower;
+/// # fn doc(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// let spine = commands
+///     .spawn((SkeletonDataHandle::default(), SpineLoader::without_children()))
+///     .id();
+/// commands.spawn((
+///     Sprite::from_image(asset_server.load("sword.png")),
+///     SpineBoneFollower {
+///         offset: Vec3::new(20., 0., 0.1),
+///         ..SpineBoneFollower::new(spine, "gun-tip")
+///     },
+/// ));
+/// # }
+/// ```
+///
+/// The follower can be anywhere in the hierarchy. As a child of the Spine entity it follows the
+/// bone exactly, otherwise it is placed through the [`GlobalTransform`]s of the Spine entity and
+/// its own parent, which are those of the previous frame. Followers wait for the Spine to be
+/// loaded, and unknown bones are skipped with a warning.
+///
+/// [`SpineSystem::UpdateAnimation`]: crate::SpineSystem::UpdateAnimation
+/// [`SpineSync`]: crate::SpineSync
+/// [`SpineLoader::without_children`]: crate::SpineLoader::without_children
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[require(Transform, SpineBoneFollowerState)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+pub struct SpineBoneFollower {
+    /// The entity containing the [`Spine`] component.
+    pub spine: Entity,
+    pub bone: String,
+    /// The position of this entity in the bone's coordinates. The z coordinate is relative to the
+    /// Spine entity, for layering.
+    pub offset: Vec3,
+    /// Rotate this entity with the bone. Otherwise, its own rotation is kept.
+    pub follow_rotation: bool,
+    /// Scale this entity with the bone. Otherwise, its own scale is kept.
+    pub follow_scale: bool,
+}
+
+impl SpineBoneFollower {
+    /// Follow the position and rotation of `bone`, but not its scale.
+    pub fn new(spine: Entity, bone: &str) -> Self {
+        Self {
+            spine,
+            bone: bone.to_owned(),
+            offset: Vec3::ZERO,
+            follow_rotation: true,
+            follow_scale: false,
+        }
+    }
+}
+
+/// The bone found for a [`SpineBoneFollower`], looked up again whenever the follower or the
+/// skeleton changes.
+#[derive(Component, Default)]
+pub(crate) struct SpineBoneFollowerState {
+    bone: Option<BoneHandle>,
+}
+
+pub(crate) fn spine_bone_followers(
+    mut follower_query: Query<(
+        Ref<SpineBoneFollower>,
+        &mut SpineBoneFollowerState,
+        &mut Transform,
+        Option<&ChildOf>,
+    )>,
+    spine_query: Query<(Ref<Spine>, &GlobalTransform)>,
+    parent_query: Query<&GlobalTransform>,
+) {
+    for (follower, mut state, mut transform, child_of) in follower_query.iter_mut() {
+        let Ok((spine, spine_global_transform)) = spine_query.get(follower.spine) else {
+            continue;
+        };
+        if follower.is_changed() || spine.is_added() {
+            state.bone = spine
+                .skeleton
+                .find_bone(&follower.bone)
+                .map(|bone| bone.handle());
+            if state.bone.is_none() {
+                warn!("cannot follow unknown spine bone: {}", follower.bone);
+            }
+        }
+        let Some(bone) = state.bone.and_then(|bone| bone.get(&spine.skeleton)) else {
+            continue;
+        };
+
+        let mut bone_transform = bone_world_transform(&bone);
+        let (x, y) = bone.local_to_world(follower.offset.x, follower.offset.y);
+        bone_transform.translation = Vec3::new(x, y, follower.offset.z);
+        let parent = child_of.map(ChildOf::parent);
+        let target = if parent == Some(follower.spine) {
+            bone_transform
+        } else {
+            let global_transform = spine_global_transform.mul_transform(bone_transform);
+            match parent.and_then(|parent| parent_query.get(parent).ok()) {
+                Some(parent_global_transform) => {
+                    global_transform.reparented_to(parent_global_transform)
+                }
+                None => global_transform.compute_transform(),
+            }
+        };
+
+        transform.translation = target.translation;
+        if follower.follow_rotation {
+            transform.rotation = target.rotation;
+        }
+        if follower.follow_scale {
+            transform.scale = target.scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use rusty_spine::Physics;
+
+    use super::{SpineBoneFollower, spine_bone_followers};
+    use crate::{SpineBone, SpineLoader, test_support::spineboy};
+
+    #[test]
+    fn follows_without_bone_entities() {
+        let mut world = World::new();
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0.1, Physics::Update);
+        let bone = spine.skeleton.find_bone("gun-tip").unwrap();
+        let bone_translation = Vec3::new(bone.world_x(), bone.world_y(), 0.5);
+        let spine_translation = Vec3::new(100., 50., 0.);
+        let spine = world
+            .spawn((
+                spine,
+                SpineLoader::without_children(),
+                GlobalTransform::from_translation(spine_translation),
+            ))
+            .id();
+        let follower = SpineBoneFollower {
+            offset: Vec3::new(0., 0., 0.5),
+            ..SpineBoneFollower::new(spine, "gun-tip")
+        };
+        let child = world.spawn((follower.clone(), ChildOf(spine))).id();
+        let detached = world.spawn(follower).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(spine_bone_followers);
+        schedule.run(&mut world);
+
+        assert!(world.query::<&SpineBone>().iter(&world).next().is_none());
+        let translation = |entity| world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation(child).abs_diff_eq(bone_translation, 1e-3));
+        assert!(translation(detached).abs_diff_eq(spine_translation + bone_translation, 1e-3));
+    }
+}
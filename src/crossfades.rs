@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
-use rusty_spine::AnimationStateData;
+use rusty_spine::{AnimationStateData, SkeletonData};
+
+use crate::{Spine, SpineMixTable, SpineMixTableHandle};
 
 /// Crossfade data to apply to [`rusty_spine::AnimationStateData`]. Allows automated crossfading
 /// between animations.
@@ -20,34 +22,209 @@ use rusty_spine::AnimationStateData;
 /// // Apply in the other direction too
 /// crossfades.add("run", "walk", 0.2);
 ///
+/// // Blend from any animation into idle for 0.3 secs
+/// crossfades.add("*", "idle", 0.3);
+///
+/// // Blend every other pair for 0.1 secs
+/// crossfades.set_default_mix(0.1);
+///
 /// commands.spawn((
 ///     SkeletonDataHandle::default(),
 ///     crossfades,
 /// ));
 /// # }
 /// ```
-
+///
+/// Changes to this component are applied to the live [`Spine`] before the next
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation). Mixes only affect
+/// animations started after they are applied.
+///
+/// When several entries match a pair of animations, the most specific one is used: `from -> to`,
+/// then `from -> *`, then `* -> to`, then the default mix.
+///
+/// To share the same mixes between many entities, see [`SpineMixTable`]. Crossfades on an entity
+/// with a [`SpineMixTableHandle`] override the mixes of the table.
 #[derive(Component, Default, Debug, Clone, Reflect)]
 #[reflect(Component, Default, Debug, Clone)]
 pub struct Crossfades {
     mix_durations: HashMap<(String, String), f32>,
+    default_mix: Option<f32>,
 }
 
 impl Crossfades {
     pub fn new() -> Self {
         Self {
             mix_durations: HashMap::new(),
+            default_mix: None,
         }
     }
 
+    /// Set the mix duration from one animation to another. Either name can be `"*"` to match any
+    /// animation.
     pub fn add(&mut self, from: &str, to: &str, mix_duration: f32) {
         self.mix_durations
             .insert((from.to_owned(), to.to_owned()), mix_duration);
     }
 
-    pub(crate) fn apply(&self, animation_state_data: &mut AnimationStateData) {
-        for ((from, to), mix_duration) in self.mix_durations.iter() {
-            animation_state_data.set_mix_by_name(from, to, *mix_duration);
+    pub fn remove(&mut self, from: &str, to: &str) {
+        self.mix_durations.remove(&(from.to_owned(), to.to_owned()));
+    }
+
+    /// The mix duration used for pairs of animations without an entry (default: `0.0`).
+    pub fn default_mix(&self) -> f32 {
+        self.default_mix.unwrap_or(0.)
+    }
+
+    pub fn set_default_mix(&mut self, default_mix: f32) {
+        self.default_mix = Some(default_mix);
+    }
+
+    /// Returns true if no mix or default mix was set.
+    pub fn is_empty(&self) -> bool {
+        self.mix_durations.is_empty() && self.default_mix.is_none()
+    }
+
+    /// Adds every mix from `other`, replacing mixes between the same animations. The default mix
+    /// is replaced too, if `other` sets one.
+    pub fn extend(&mut self, other: &Crossfades) {
+        self.mix_durations.extend(
+            other
+                .mix_durations
+                .iter()
+                .map(|(animations, mix_duration)| (animations.clone(), *mix_duration)),
+        );
+        if other.default_mix.is_some() {
+            self.default_mix = other.default_mix;
         }
     }
+
+    /// The mix duration from one animation to another, after resolving wildcards.
+    pub fn mix(&self, from: &str, to: &str) -> f32 {
+        [(from, to), (from, "*"), ("*", to)]
+            .into_iter()
+            .find_map(|(from, to)| {
+                self.mix_durations
+                    .get(&(from.to_owned(), to.to_owned()))
+                    .copied()
+            })
+            .unwrap_or(self.default_mix())
+    }
+
+    /// Applies these crossfades, returning every pair of animations given a mix.
+    pub(crate) fn apply(
+        &self,
+        animation_state_data: &mut AnimationStateData,
+        skeleton_data: &SkeletonData,
+    ) -> HashSet<(String, String)> {
+        animation_state_data.set_default_mix(self.default_mix());
+        let mut applied = HashSet::new();
+        if self
+            .mix_durations
+            .keys()
+            .any(|(from, to)| from == "*" || to == "*")
+        {
+            let animations: Vec<String> = skeleton_data
+                .animations()
+                .map(|animation| animation.name().to_owned())
+                .collect();
+            for from in animations.iter() {
+                for to in animations.iter() {
+                    let mix_duration = self.mix(from, to);
+                    if mix_duration != self.default_mix() {
+                        animation_state_data.set_mix_by_name(from, to, mix_duration);
+                        applied.insert((from.clone(), to.clone()));
+                    }
+                }
+            }
+        } else {
+            for ((from, to), mix_duration) in self.mix_durations.iter() {
+                animation_state_data.set_mix_by_name(from, to, *mix_duration);
+                applied.insert((from.clone(), to.clone()));
+            }
+        }
+        applied
+    }
+}
+
+/// The pairs of animations given a mix by [`Crossfades`] on a live [`Spine`].
+#[derive(Component, Default, Debug, Clone)]
+pub(crate) struct SpineCrossfadesState {
+    pub(crate) applied: HashSet<(String, String)>,
+    /// If true, the [`AnimationStateData`] is shared with other entities through a
+    /// [`SpineMixTable`], so it cannot be changed.
+    pub(crate) shared: bool,
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_crossfades(
+    mut spine_query: Query<
+        (
+            Entity,
+            &mut Spine,
+            &Crossfades,
+            Option<&SpineMixTableHandle>,
+            &mut SpineCrossfadesState,
+        ),
+        Changed<Crossfades>,
+    >,
+    mix_tables: Res<Assets<SpineMixTable>>,
+) {
+    for (entity, mut spine, crossfades, mix_table_handle, mut crossfades_state) in
+        spine_query.iter_mut()
+    {
+        if crossfades_state.shared {
+            if !crossfades.is_empty() {
+                warn!(
+                    "spine entity {entity} shares its mix table, so changes to its crossfades are \
+                    ignored. Add crossfades before the skeleton is ready to override the mix table."
+                );
+            }
+            continue;
+        }
+        let mix_table = mix_table_handle.and_then(|handle| mix_tables.get(&handle.0));
+        let crossfades = SpineMixTable::layer(mix_table, Some(crossfades));
+        let Spine(controller) = spine.as_mut();
+        let skeleton_data = controller.skeleton.data();
+        let mut animation_state_data = controller.animation_state.data_mut();
+        let applied = crossfades.apply(&mut animation_state_data, &skeleton_data);
+        // pairs without a mix anymore fall back to the default mix
+        for (from, to) in crossfades_state.applied.difference(&applied) {
+            animation_state_data.set_mix_by_name(from, to, crossfades.default_mix());
+        }
+        crossfades_state.applied = applied;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusty_spine::AnimationStateData;
+
+    use super::Crossfades;
+    use crate::test_support::spineboy_data;
+
+    #[test]
+    fn wildcards_resolve_against_skeleton() {
+        let skeleton_data = spineboy_data();
+        let mut crossfades = Crossfades::new();
+        crossfades.set_default_mix(0.1);
+        crossfades.add("walk", "*", 0.4);
+        crossfades.add("*", "idle", 0.3);
+        crossfades.add("walk", "idle", 0.5);
+        let mut animation_state_data = AnimationStateData::new(skeleton_data.clone());
+        let applied = crossfades.apply(&mut animation_state_data, &skeleton_data);
+
+        let mut mix = |from: &str, to: &str| {
+            animation_state_data.get_mix(
+                &skeleton_data.find_animation(from).unwrap(),
+                &skeleton_data.find_animation(to).unwrap(),
+            )
+        };
+        assert_eq!(mix("walk", "idle"), 0.5);
+        assert_eq!(mix("walk", "run"), 0.4);
+        assert_eq!(mix("run", "idle"), 0.3);
+        assert_eq!(mix("jump", "idle"), 0.3);
+        assert_eq!(mix("run", "jump"), 0.1);
+        assert!(applied.contains(&("jump".to_owned(), "idle".to_owned())));
+        assert!(!applied.contains(&("run".to_owned(), "jump".to_owned())));
+    }
 }
@@ -0,0 +1,236 @@
+//! Entity-targeted Spine events, for use with observers.
+
+use bevy::prelude::*;
+
+use crate::{SpineEvent, SpineName, SpineTrackInfo};
+
+/// Triggered on a Spine entity when an animation starts, see [`SpineEvent::Start`].
+///
+/// Like every Spine [`EntityEvent`], this propagates up the hierarchy through [`ChildOf`], so a
+/// parent entity can observe the Spine entities it owns. While propagating, `entity` is the entity
+/// currently observing, and [`On::original_event_target`] is the Spine entity.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands
+///     .spawn(SkeletonDataHandle::default())
+///     .observe(|event: On<SpineAnimationStart>| {
+///         println!("{} started on {}", event.animation, event.entity);
+///     });
+/// # }
+/// ```
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAnimationStart {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+}
+
+/// Triggered on a Spine entity when an animation is interrupted, see [`SpineEvent::Interrupt`].
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAnimationInterrupt {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+}
+
+/// Triggered on a Spine entity when an animation ends, see [`SpineEvent::End`].
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAnimationEnd {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+}
+
+/// Triggered on a Spine entity when an animation completes a loop, see [`SpineEvent::Complete`].
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAnimationComplete {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+}
+
+/// Triggered on a Spine entity when a track entry is disposed, see [`SpineEvent::Dispose`].
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineAnimationDispose {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+}
+
+/// Triggered on a Spine entity when a user event keyed in an animation fires, see
+/// [`SpineEvent::Event`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands
+///     .spawn(SkeletonDataHandle::default())
+///     .observe(|event: On<SpineUserEvent>| {
+///         if event.name == "footstep" {
+///             println!("footstep from {}", event.original_event_target());
+///         }
+///     });
+/// # }
+/// ```
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct SpineUserEvent {
+    pub entity: Entity,
+    pub animation: SpineName,
+    pub track: SpineTrackInfo,
+    pub name: SpineName,
+    /// The time this event is keyed at in the animation.
+    pub time: f32,
+    /// The time in the animation when this event fired.
+    pub animation_time: f32,
+    pub int: i32,
+    pub float: f32,
+    pub string: SpineName,
+    pub audio_path: SpineName,
+    pub volume: f32,
+    pub balance: f32,
+}
+
+pub(crate) fn trigger_spine_event(commands: &mut Commands, event: &SpineEvent) {
+    match event.clone() {
+        SpineEvent::Start {
+            entity,
+            animation,
+            track,
+        } => {
+            commands.trigger(SpineAnimationStart {
+                entity,
+                animation,
+                track,
+            });
+        }
+        SpineEvent::Interrupt {
+            entity,
+            animation,
+            track,
+        } => {
+            commands.trigger(SpineAnimationInterrupt {
+                entity,
+                animation,
+                track,
+            });
+        }
+        SpineEvent::End {
+            entity,
+            animation,
+            track,
+        } => {
+            commands.trigger(SpineAnimationEnd {
+                entity,
+                animation,
+                track,
+            });
+        }
+        SpineEvent::Complete {
+            entity,
+            animation,
+            track,
+        } => {
+            commands.trigger(SpineAnimationComplete {
+                entity,
+                animation,
+                track,
+            });
+        }
+        SpineEvent::Dispose {
+            entity,
+            animation,
+            track,
+        } => {
+            commands.trigger(SpineAnimationDispose {
+                entity,
+                animation,
+                track,
+            });
+        }
+        SpineEvent::Event {
+            entity,
+            animation,
+            track,
+            name,
+            time,
+            animation_time,
+            int,
+            float,
+            string,
+            audio_path,
+            volume,
+            balance,
+        } => {
+            commands.trigger(SpineUserEvent {
+                entity,
+                animation,
+                track,
+                name,
+                time,
+                animation_time,
+                int,
+                float,
+                string,
+                audio_path,
+                volume,
+                balance,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{SpineAnimationComplete, trigger_spine_event};
+    use crate::{SpineEvent, SpineTrackEntryId, SpineTrackInfo};
+
+    #[derive(Resource, Default)]
+    struct Observed(Vec<(Entity, Entity)>);
+
+    #[test]
+    fn propagates_to_parent() {
+        let mut world = World::new();
+        world.init_resource::<Observed>();
+        let parent = world
+            .spawn_empty()
+            .observe(
+                |event: On<SpineAnimationComplete>, mut observed: ResMut<Observed>| {
+                    observed
+                        .0
+                        .push((event.entity, event.original_event_target()));
+                },
+            )
+            .id();
+        let spine = world.spawn(ChildOf(parent)).id();
+
+        trigger_spine_event(
+            &mut world.commands(),
+            &SpineEvent::Complete {
+                entity: spine,
+                animation: "walk".into(),
+                track: SpineTrackInfo {
+                    id: SpineTrackEntryId(0),
+                    track_index: 0,
+                    track_time: 1.,
+                    animation_duration: 1.,
+                    loop_count: 1,
+                },
+            },
+        );
+        world.flush();
+
+        assert_eq!(world.resource::<Observed>().0, vec![(parent, spine)]);
+    }
+}
@@ -1,7 +1,7 @@
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{collections::HashSet, fmt::Debug, hash::Hash, marker::PhantomData};
 
 use bevy::prelude::*;
-use rusty_spine::Physics;
+use rusty_spine::{Bone, BoneHandle, Physics, Skeleton};
 
 use crate::{Spine, SpineBone, SpineSystem};
 
@@ -140,12 +140,16 @@ impl<T: SpineSynchronizer, A: SystemSet + Copy> Plugin for SpineSynchronizerPlug
 /// Synchronizes [`SpineBone`] transforms to the Spine skeleton bone transforms.
 pub fn spine_sync_entities<S: SpineSynchronizer>(
     mut bone_query: Query<(&mut Transform, &SpineBone)>,
-    spine_query: Query<&Spine, With<S>>,
+    spine_query: Query<(&Spine, Option<&SpineSyncMode>), With<S>>,
 ) {
     for (mut bone_transform, bone) in bone_query.iter_mut() {
-        if let Ok(spine) = spine_query.get(bone.spine_entity)
+        if let Ok((spine, sync_mode)) = spine_query.get(bone.spine_entity)
             && let Some(bone) = bone.handle.get(&spine.skeleton)
         {
+            if sync_mode == Some(&SpineSyncMode::World) {
+                set_world_transform(&mut bone_transform, &bone);
+                continue;
+            }
             bone_transform.translation.x = bone.x();
             bone_transform.translation.y = bone.y();
             bone_transform.rotation = Quat::from_axis_angle(Vec3::Z, bone.rotation().to_radians());
@@ -158,34 +162,70 @@ pub fn spine_sync_entities<S: SpineSynchronizer>(
 /// Synchronizes Spine skeleton bones to [`SpineBone`] transforms.
 pub fn spine_sync_bones<S: SpineSynchronizer>(
     mut bone_query: Query<(&mut Transform, &SpineBone)>,
-    mut spine_query: Query<&mut Spine, With<S>>,
+    mut spine_query: Query<(&mut Spine, Option<&SpineSyncMode>), With<S>>,
 ) {
+    let mut world_bones = vec![];
     for (bone_transform, bone) in bone_query.iter_mut() {
-        if let Ok(mut spine) = spine_query.get_mut(bone.spine_entity)
-            && let Some(mut bone) = bone.handle.get_mut(&mut spine.skeleton)
-        {
-            bone.set_x(bone_transform.translation.x);
-            bone.set_y(bone_transform.translation.y);
-            let ang = bone_transform.rotation * Vec3::X;
-            bone.set_rotation(ang.y.atan2(ang.x).to_degrees());
-            bone.set_scale_x(bone_transform.scale.x);
-            bone.set_scale_y(bone_transform.scale.y);
+        if let Ok((mut spine, sync_mode)) = spine_query.get_mut(bone.spine_entity) {
+            if sync_mode == Some(&SpineSyncMode::World) {
+                // compare against the world transforms before any bone is moved
+                if let Some(spine_bone) = bone.handle.get(&spine.skeleton)
+                    && !world_transform_eq(&bone_transform, &bone_world_transform(&spine_bone))
+                {
+                    world_bones.push((
+                        bone.spine_entity,
+                        spine_bone.data().index(),
+                        bone.handle,
+                        *bone_transform,
+                    ));
+                }
+                continue;
+            }
+            if let Some(mut bone) = bone.handle.get_mut(&mut spine.skeleton) {
+                bone.set_x(bone_transform.translation.x);
+                bone.set_y(bone_transform.translation.y);
+                let ang = bone_transform.rotation * Vec3::X;
+                bone.set_rotation(ang.y.atan2(ang.x).to_degrees());
+                bone.set_scale_x(bone_transform.scale.x);
+                bone.set_scale_y(bone_transform.scale.y);
+            }
+        }
+    }
+    // parents come before their children in the skeleton, so moving a parent moves its children
+    // before they are compared
+    world_bones.sort_by_key(|(spine_entity, index, _, _)| (*spine_entity, *index));
+    for (spine_entity, _, handle, bone_transform) in world_bones {
+        if let Ok((mut spine, _)) = spine_query.get_mut(spine_entity) {
+            set_bone_world_transform(&mut spine.skeleton, handle, &bone_transform);
         }
     }
-    for mut spine in spine_query.iter_mut() {
+    for (mut spine, _) in spine_query.iter_mut() {
         spine.0.skeleton.update_world_transform(Physics::Update);
     }
 }
 
 /// Synchronizes [`SpineBone`] transforms with the final, applied Spine bones transforms.
 pub fn spine_sync_entities_applied<S: SpineSynchronizer>(
-    mut bone_query: Query<(&mut Transform, &SpineBone)>,
-    spine_query: Query<&Spine, With<S>>,
+    mut bone_query: Query<(Entity, &mut Transform, &SpineBone)>,
+    spine_query: Query<(&Spine, Option<&SpineSyncMode>), With<S>>,
+    mut sheared: Local<HashSet<Entity>>,
 ) {
-    for (mut bone_transform, bone) in bone_query.iter_mut() {
-        if let Ok(spine) = spine_query.get(bone.spine_entity)
+    for (bone_entity, mut bone_transform, bone) in bone_query.iter_mut() {
+        if let Ok((spine, sync_mode)) = spine_query.get(bone.spine_entity)
             && let Some(bone) = bone.handle.get(&spine.skeleton)
         {
+            if sync_mode == Some(&SpineSyncMode::World) {
+                set_world_transform(&mut bone_transform, &bone);
+                let shear = bone_world_shear(&bone);
+                if shear.abs() > SHEAR_TOLERANCE && sheared.insert(bone_entity) {
+                    warn!(
+                        "spine bone {} is sheared by {shear:.2} degrees in world space, which \
+                        cannot be represented by the Transform of entity {bone_entity}",
+                        bone.data().name()
+                    );
+                }
+                continue;
+            }
             bone_transform.translation.x = bone.applied_x();
             bone_transform.translation.y = bone.applied_y();
             bone_transform.rotation =
@@ -196,6 +236,162 @@ pub fn spine_sync_entities_applied<S: SpineSynchronizer>(
     }
 }
 
+/// How [`SpineBone`] entities are laid out and synchronized, see [`SpineSync`].
+///
+/// Add to a Spine entity when spawning it, since bone entities are spawned accordingly.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::{SkeletonDataHandle, SpineSync, SpineSyncMode};
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineSync,
+///     SpineSyncMode::World,
+/// ));
+/// # }
+/// ```
+#[derive(Component, Default, Debug, Hash, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, Hash, PartialEq, Clone)]
+pub enum SpineSyncMode {
+    /// Bone entities are nested like the bones of the skeleton, and their [`Transform`]s hold the
+    /// local x, y, rotation and scale of each bone.
+    ///
+    /// Shear and the bone inherit modes (such as `noRotationOrReflection` or `noScale`) are not
+    /// represented, so bone entities may not line up with the rendered skeleton on rigs that use
+    /// them.
+    #[default]
+    Local,
+    /// Bone entities are direct children of the Spine entity, and their [`Transform`]s are
+    /// decomposed from the world transform of each bone (see [`bone_world_transform`]), so they
+    /// line up with the rendered skeleton whatever the inherit modes.
+    ///
+    /// World transforms including shear cannot be represented by a [`Transform`]. Such bones are
+    /// reported once with a warning, and their entities keep the rotation of the bone's x axis.
+    ///
+    /// Moving bone entities during [`SpineSyncSet::DuringSync`] sets their world position,
+    /// rotation and scale. Bones whose entities were not moved keep their local pose.
+    World,
+}
+
+/// Shear, in degrees, tolerated before warning about it.
+const SHEAR_TOLERANCE: f32 = 0.1;
+
+/// The world transform of a bone, relative to the Spine entity, decomposed into a [`Transform`].
+///
+/// The rotation and scale follow the bone's x axis, and the y scale keeps the area (and any
+/// reflection) of the world transform. Any shear is lost, see [`bone_world_shear`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// use bevy_spine::bone_world_transform;
+///
+/// fn print_head(spine_query: Query<(&Spine, &GlobalTransform)>) {
+///     for (spine, global_transform) in spine_query.iter() {
+///         if let Some(head) = spine.skeleton.find_bone("head") {
+///             let head = global_transform.mul_transform(bone_world_transform(&head));
+///             println!("head at {}", head.translation());
+///         }
+///     }
+/// }
+/// ```
+pub fn bone_world_transform(bone: &Bone) -> Transform {
+    let scale_x = bone.a().hypot(bone.c());
+    let scale_y = if scale_x == 0. {
+        bone.b().hypot(bone.d())
+    } else {
+        (bone.a() * bone.d() - bone.b() * bone.c()) / scale_x
+    };
+    Transform {
+        translation: Vec3::new(bone.world_x(), bone.world_y(), 0.),
+        rotation: Quat::from_rotation_z(bone.c().atan2(bone.a())),
+        scale: Vec3::new(scale_x, scale_y, 1.),
+    }
+}
+
+/// The shear of a bone's world transform in degrees, which [`bone_world_transform`] cannot
+/// represent: how far the bone's y axis leans away from perpendicular to its x axis.
+pub fn bone_world_shear(bone: &Bone) -> f32 {
+    let scale_x = bone.a().hypot(bone.c());
+    let determinant = bone.a() * bone.d() - bone.b() * bone.c();
+    if scale_x == 0. || determinant == 0. {
+        return 0.;
+    }
+    // the y axis, in the frame of the x axis
+    let shear = (bone.a() * bone.b() + bone.c() * bone.d()) / scale_x;
+    let scale_y = determinant / scale_x;
+    (shear / scale_y).atan().to_degrees()
+}
+
+/// Sets the world transform of a bone entity, keeping its z translation.
+fn set_world_transform(bone_transform: &mut Transform, bone: &Bone) {
+    let z = bone_transform.translation.z;
+    *bone_transform = bone_world_transform(bone);
+    bone_transform.translation.z = z;
+}
+
+fn world_transform_eq(a: &Transform, b: &Transform) -> bool {
+    a.translation
+        .truncate()
+        .abs_diff_eq(b.translation.truncate(), 1e-3)
+        && a.rotation.abs_diff_eq(b.rotation, 1e-5)
+        && a.scale.truncate().abs_diff_eq(b.scale.truncate(), 1e-5)
+}
+
+/// Moves a bone so that its world transform matches `bone_transform`, by changing its local pose.
+fn set_bone_world_transform(
+    skeleton: &mut Skeleton,
+    handle: BoneHandle,
+    bone_transform: &Transform,
+) {
+    let (skeleton_x, skeleton_y) = (skeleton.x(), skeleton.y());
+    let (skeleton_scale_x, skeleton_scale_y) = (skeleton.scale_x(), skeleton.scale_y());
+    let Some(mut bone) = handle.get_mut(skeleton) else {
+        return;
+    };
+    // the parent may have moved
+    bone.update_world_transform();
+
+    let (world_x, world_y) = (bone_transform.translation.x, bone_transform.translation.y);
+    let (x, y) = match bone.parent() {
+        Some(parent) => parent.world_to_local(world_x, world_y),
+        None => (
+            (world_x - skeleton_x) / skeleton_scale_x,
+            (world_y - skeleton_y) / skeleton_scale_y,
+        ),
+    };
+    // the direction of the x axis in the parent's space, which the bone's own scale must not skew
+    let direction = bone_transform.rotation * Vec3::X;
+    let local_direction = match bone.parent() {
+        Some(parent) => {
+            let (end_x, end_y) =
+                parent.world_to_local(world_x + direction.x, world_y + direction.y);
+            Vec2::new(end_x - x, end_y - y)
+        }
+        None => Vec2::new(
+            direction.x / skeleton_scale_x,
+            direction.y / skeleton_scale_y,
+        ),
+    };
+    let rotation = local_direction.y.atan2(local_direction.x).to_degrees() - bone.shear_x();
+    bone.set_x(x);
+    bone.set_y(y);
+    bone.set_rotation(rotation);
+    bone.update_world_transform();
+
+    // the world scale along each axis is proportional to the local scale along it
+    let current = bone_world_transform(&bone);
+    let (scale_x, scale_y) = (bone.scale_x(), bone.scale_y());
+    if current.scale.x != 0. {
+        bone.set_scale_x(scale_x * bone_transform.scale.x / current.scale.x);
+    }
+    if current.scale.y != 0. {
+        bone.set_scale_y(scale_y * bone_transform.scale.y / current.scale.y);
+    }
+    bone.update_world_transform();
+}
+
 /// A [`Component`] which synchronizes child (bone) entities to to a [`Spine`] rig (see
 /// [`SpineBone`]).
 ///
@@ -210,7 +406,8 @@ pub fn spine_sync_entities_applied<S: SpineSynchronizer>(
 /// # }
 /// ```
 ///
-/// To coordinate systems around synchronization, see [`SpineSyncSet`].
+/// To coordinate systems around synchronization, see [`SpineSyncSet`]. To synchronize bone entities
+/// with the world transforms of the bones instead, see [`SpineSyncMode`].
 ///
 /// If multiple synchronization steps are needed, additional sync components can be created (see
 /// [`SpineSynchronizerPlugin`]).
@@ -267,3 +464,161 @@ pub type SpineSyncSystem = SpineSynchronizerSystem<SpineSync>;
 /// ```
 pub type SpineSyncSet = SpineSynchronizerSet<SpineSync>;
 pub(crate) type SpineSyncPlugin = SpineSynchronizerPlugin<SpineSync, SpineSystem>;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::prelude::*;
+    use rusty_spine::Physics;
+
+    use super::{SpineSync, SpineSyncMode, SpineSyncPlugin, SpineSyncSet, bone_world_transform};
+    use crate::{Spine, SpineBone, spawn_bones, test_support::spineboy};
+
+    fn running_spineboy() -> Spine {
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "run", true)
+            .unwrap();
+        spine.update(0.3, Physics::Update);
+        spine
+    }
+
+    /// Spawns a running spineboy with world space bone entities, under a transformed parent.
+    fn world_sync_app(spine: Spine) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, SpineSyncPlugin::first()));
+        let spine_entity = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(100., 50., 0.)
+                    .with_rotation(Quat::from_rotation_z(0.5))
+                    .with_scale(Vec3::new(2., 1.5, 1.)),
+                SpineSync,
+                SpineSyncMode::World,
+            ))
+            .id();
+        let root = spine.skeleton.bones().next().unwrap().handle();
+        app.world_mut()
+            .commands()
+            .entity(spine_entity)
+            .with_children(|spawner| {
+                spawn_bones(
+                    spine_entity,
+                    None,
+                    spawner,
+                    &spine.skeleton,
+                    root,
+                    None,
+                    None,
+                    true,
+                    &mut HashMap::new(),
+                );
+            });
+        app.world_mut().flush();
+        app.world_mut().entity_mut(spine_entity).insert(spine);
+        (app, spine_entity)
+    }
+
+    #[test]
+    fn bone_world_transform_maps_bone_space() {
+        let spine = running_spineboy();
+        for bone in spine.skeleton.bones() {
+            let transform = bone_world_transform(&bone);
+            // the x axis is kept whatever the shear
+            let point = transform.transform_point(Vec3::new(30., 0., 0.));
+            let (x, y) = bone.local_to_world(30., 0.);
+            assert!(
+                point.truncate().abs_diff_eq(Vec2::new(x, y), 1e-2),
+                "{}",
+                bone.data().name()
+            );
+        }
+    }
+
+    #[test]
+    fn world_sync_places_bone_entities() {
+        let (mut app, spine_entity) = world_sync_app(running_spineboy());
+        app.update();
+
+        let world = app.world_mut();
+        let spine_global_transform = *world.get::<GlobalTransform>(spine_entity).unwrap();
+        let mut bone_query = world.query::<(&SpineBone, &ChildOf, &GlobalTransform)>();
+        let spine = world.get::<Spine>(spine_entity).unwrap();
+        let mut bone_count = 0;
+        for (spine_bone, child_of, global_transform) in bone_query.iter(world) {
+            // bone entities are flat, directly under the Spine entity
+            assert_eq!(child_of.parent(), spine_entity);
+            let bone = spine_bone.handle.get(&spine.skeleton).unwrap();
+            let expected = spine_global_transform.transform_point(Vec3::new(
+                bone.world_x(),
+                bone.world_y(),
+                0.,
+            ));
+            assert!(
+                global_transform.translation().abs_diff_eq(expected, 1e-2),
+                "{}: {} != {expected}",
+                spine_bone.name,
+                global_transform.translation()
+            );
+            let x_axis = spine_global_transform.affine().transform_vector3(Vec3::new(
+                bone.a(),
+                bone.c(),
+                0.,
+            ));
+            assert!(
+                global_transform
+                    .affine()
+                    .transform_vector3(Vec3::X)
+                    .abs_diff_eq(x_axis, 1e-2),
+                "{}",
+                spine_bone.name
+            );
+            bone_count += 1;
+        }
+        assert_eq!(bone_count, spine.skeleton.bones().count());
+    }
+
+    #[test]
+    fn world_sync_writes_moved_bones_back() {
+        let spine = running_spineboy();
+        let (expected, hip_pose) = {
+            let head = spine.skeleton.find_bone("head").unwrap();
+            let mut expected = bone_world_transform(&head);
+            expected.translation += Vec3::new(10., 5., 0.);
+            expected.rotate_z(0.2);
+            let hip = spine.skeleton.find_bone("hip").unwrap();
+            (expected, (hip.x(), hip.y(), hip.rotation()))
+        };
+
+        let (mut app, spine_entity) = world_sync_app(spine);
+        app.add_systems(
+            Update,
+            (|mut bone_query: Query<(&mut Transform, &SpineBone)>| {
+                for (mut transform, bone) in bone_query.iter_mut() {
+                    if bone.name == "head" {
+                        transform.translation += Vec3::new(10., 5., 0.);
+                        transform.rotate_z(0.2);
+                    }
+                }
+            })
+            .in_set(SpineSyncSet::DuringSync),
+        );
+        app.update();
+
+        let spine = app.world().get::<Spine>(spine_entity).unwrap();
+        let head = bone_world_transform(&spine.skeleton.find_bone("head").unwrap());
+        assert!(
+            head.translation.abs_diff_eq(expected.translation, 1e-2),
+            "{} != {}",
+            head.translation,
+            expected.translation
+        );
+        assert!((head.rotation * Vec3::X).abs_diff_eq(expected.rotation * Vec3::X, 1e-4));
+        assert!(head.scale.abs_diff_eq(expected.scale, 1e-4));
+        // bones whose entities were not moved keep their local pose
+        let hip = spine.skeleton.find_bone("hip").unwrap();
+        assert_eq!((hip.x(), hip.y(), hip.rotation()), hip_pose);
+    }
+}
@@ -0,0 +1,325 @@
+//! Bounding box frame data, precomputed for every animation of a skeleton.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rusty_spine::Skeleton;
+use serde::{Deserialize, Serialize};
+
+use crate::{SkeletonData, Spine, SpinePoseSampler, assets::RonAsset};
+
+/// The bounding box attachments visible in every animation of a skeleton, sampled at a fixed
+/// rate. Loaded from `.spineframes.ron` files, or created with [`SpineFrameData::bake`].
+///
+/// Useful for hitboxes and hurtboxes authored in Spine, which need to be known ahead of time (for
+/// example by AI or for netcode) and must not depend on the frame rate.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineFrameData;
+/// // a tool which bakes frame data for a skeleton once it is loaded
+/// fn bake(skeleton_data_assets: Res<Assets<SkeletonData>>) {
+///     for (_, skeleton_data) in skeleton_data_assets.iter() {
+///         if let Some(frame_data) = SpineFrameData::bake(skeleton_data, 60.) {
+///             let ron = frame_data.to_ron().unwrap();
+///             // .. save to a `.spineframes.ron` file ..
+///         }
+///     }
+/// }
+/// ```
+///
+/// To look up the bounding boxes of a Spine entity, see [`SpineHitboxes`].
+#[derive(Asset, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct SpineFrameData {
+    /// Frames sampled per second.
+    pub fps: f32,
+    pub animations: HashMap<String, SpineAnimationFrames>,
+}
+
+/// The frames of one animation in [`SpineFrameData`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct SpineAnimationFrames {
+    pub duration: f32,
+    /// Frame `n` is sampled at `n / fps` seconds.
+    pub frames: Vec<SpineHitboxFrame>,
+}
+
+/// The bounding boxes visible on one frame, see [`SpineAnimationFrames`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct SpineHitboxFrame {
+    pub hitboxes: Vec<SpineHitbox>,
+}
+
+/// A bounding box attachment.
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct SpineHitbox {
+    pub slot: String,
+    pub attachment: String,
+    /// The polygon of the bounding box, in skeleton space.
+    pub polygon: Vec<Vec2>,
+}
+
+impl SpineFrameData {
+    /// Sample every animation of a skeleton at `fps` frames per second, or return `None` if the
+    /// skeleton is not loaded yet.
+    pub fn bake(skeleton_data: &SkeletonData, fps: f32) -> Option<Self> {
+        let skeleton_data = skeleton_data.skeleton_data()?;
+        let mut sampler = SpinePoseSampler::new(skeleton_data.clone());
+        let mut animations = HashMap::new();
+        for animation in skeleton_data.animations() {
+            let duration = animation.duration();
+            let frame_count = (duration * fps).ceil() as usize + 1;
+            let mut frames = Vec::with_capacity(frame_count);
+            for frame in 0..frame_count {
+                let time = (frame as f32 / fps).min(duration);
+                sampler.pose_animation(&animation, time, false);
+                frames.push(SpineHitboxFrame {
+                    hitboxes: hitboxes(sampler.skeleton()),
+                });
+            }
+            animations.insert(
+                animation.name().to_owned(),
+                SpineAnimationFrames { duration, frames },
+            );
+        }
+        Some(Self { fps, animations })
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// The frame of an animation at a time, or `None` if the animation was not baked.
+    pub fn frame(&self, animation: &str, time: f32) -> Option<&SpineHitboxFrame> {
+        let animation = self.animations.get(animation)?;
+        let index = (time.max(0.) * self.fps).floor() as usize;
+        animation
+            .frames
+            .get(index)
+            .or_else(|| animation.frames.last())
+    }
+}
+
+/// The world polygons of every visible bounding box attachment.
+fn hitboxes(skeleton: &Skeleton) -> Vec<SpineHitbox> {
+    let mut hitboxes = vec![];
+    for slot in skeleton.slots() {
+        let Some(attachment) = slot.attachment() else {
+            continue;
+        };
+        let Some(bounding_box) = attachment.as_bounding_box() else {
+            continue;
+        };
+        let length = bounding_box.world_vertices_length();
+        let mut vertices = vec![0.; length as usize];
+        // SAFETY: the attachment is the one currently attached to this slot
+        unsafe {
+            bounding_box.compute_world_vertices(&slot, 0, length, &mut vertices, 0, 2);
+        }
+        hitboxes.push(SpineHitbox {
+            slot: slot.data().name().to_owned(),
+            attachment: attachment.name().to_owned(),
+            polygon: vertices
+                .chunks_exact(2)
+                .map(|vertex| Vec2::new(vertex[0], vertex[1]))
+                .collect(),
+        });
+    }
+    hitboxes
+}
+
+impl RonAsset for SpineFrameData {
+    const EXTENSIONS: &'static [&'static str] = &["spineframes.ron"];
+}
+
+/// Looks up the bounding boxes of a Spine entity in [`SpineFrameData`] every frame, using the
+/// current time of each track.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineHitboxes;
+/// # fn doc(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineHitboxes::new(asset_server.load("fighter.spineframes.ron")),
+/// ));
+/// # }
+///
+/// fn print_hitboxes(hitboxes_query: Query<(&SpineHitboxes, &GlobalTransform)>) {
+///     for (hitboxes, transform) in hitboxes_query.iter() {
+///         for hitbox in hitboxes.active() {
+///             let polygon: Vec<Vec3> = hitbox
+///                 .polygon
+///                 .iter()
+///                 .map(|vertex| transform.transform_point(vertex.extend(0.)))
+///                 .collect();
+///             println!("{} at {:?}", hitbox.attachment, polygon);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Hitboxes are updated after [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation)
+/// and are in skeleton space, scaled by the skeleton's scale. When several tracks show a bounding
+/// box on the same slot, the highest track wins.
+#[derive(Component, Default, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct SpineHitboxes {
+    pub frame_data: Handle<SpineFrameData>,
+    active: Vec<SpineHitbox>,
+}
+
+impl SpineHitboxes {
+    pub fn new(frame_data: Handle<SpineFrameData>) -> Self {
+        Self {
+            frame_data,
+            active: vec![],
+        }
+    }
+
+    /// The bounding boxes visible this frame.
+    pub fn active(&self) -> &[SpineHitbox] {
+        &self.active
+    }
+}
+
+pub(crate) fn spine_hitboxes(
+    mut hitboxes_query: Query<(&Spine, &mut SpineHitboxes)>,
+    frame_data_assets: Res<Assets<SpineFrameData>>,
+) {
+    for (spine, mut hitboxes) in hitboxes_query.iter_mut() {
+        let Some(frame_data) = frame_data_assets.get(&hitboxes.frame_data) else {
+            continue;
+        };
+        let scale = Vec2::new(spine.skeleton.scale_x(), spine.skeleton.scale_y());
+        let mut active: Vec<SpineHitbox> = vec![];
+        for track_entry in spine.animation_state.tracks().flatten() {
+            let Some(frame) =
+                frame_data.frame(track_entry.animation().name(), track_entry.animation_time())
+            else {
+                continue;
+            };
+            for hitbox in frame.hitboxes.iter() {
+                active.retain(|active| active.slot != hitbox.slot);
+                active.push(SpineHitbox {
+                    slot: hitbox.slot.clone(),
+                    attachment: hitbox.attachment.clone(),
+                    polygon: hitbox
+                        .polygon
+                        .iter()
+                        .map(|vertex| *vertex * scale)
+                        .collect(),
+                });
+            }
+        }
+        hitboxes.active = active;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    use rusty_spine::Physics;
+
+    use super::{
+        SpineAnimationFrames, SpineFrameData, SpineHitbox, SpineHitboxFrame, SpineHitboxes,
+        hitboxes, spine_hitboxes,
+    };
+    use crate::{
+        SkeletonData, SkeletonDataKind, SkeletonDataStatus,
+        test_support::{spineboy, spineboy_data},
+    };
+
+    #[test]
+    fn bake_every_animation() {
+        let spine_data = spineboy_data();
+        let skeleton_data = SkeletonData {
+            atlas_handle: Handle::default(),
+            kind: SkeletonDataKind::JsonFile(Handle::default()),
+            status: SkeletonDataStatus::Loaded(spine_data.clone()),
+            premultiplied_alpha: false,
+        };
+        let frame_data = SpineFrameData::bake(&skeleton_data, 30.).unwrap();
+        assert_eq!(frame_data.animations.len(), spine_data.animations_count());
+        let walk = &frame_data.animations["walk"];
+        assert_eq!(walk.frames.len(), (walk.duration * 30.).ceil() as usize + 1);
+    }
+
+    #[test]
+    fn hitbox_polygons_in_skeleton_space() {
+        let mut spine = spineboy();
+        assert!(hitboxes(&spine.skeleton).is_empty());
+        assert!(spine.skeleton.set_attachment("head-bb", Some("head")));
+        spine.skeleton.update_world_transform(Physics::None);
+
+        let hitboxes = hitboxes(&spine.skeleton);
+        assert_eq!(hitboxes.len(), 1);
+        assert_eq!(hitboxes[0].slot, "head-bb");
+        assert_eq!(hitboxes[0].attachment, "head");
+        // the first vertex of the bounding box, in the space of the head bone
+        let head = spine.skeleton.find_bone("head").unwrap();
+        let (x, y) = (-19.14, -70.3);
+        let expected = Vec2::new(
+            head.a() * x + head.b() * y + head.world_x(),
+            head.c() * x + head.d() * y + head.world_y(),
+        );
+        assert_eq!(hitboxes[0].polygon.len(), 6);
+        assert!(hitboxes[0].polygon[0].abs_diff_eq(expected, 1e-3));
+    }
+
+    #[test]
+    fn hitboxes_follow_track_time() {
+        let hitbox = |attachment: &str| SpineHitbox {
+            slot: "head-bb".to_owned(),
+            attachment: attachment.to_owned(),
+            polygon: vec![Vec2::new(1., 2.)],
+        };
+        let frames = ["a", "b", "c"]
+            .map(|attachment| SpineHitboxFrame {
+                hitboxes: vec![hitbox(attachment)],
+            })
+            .to_vec();
+        let frame_data = SpineFrameData {
+            fps: 10.,
+            animations: HashMap::from([(
+                "walk".to_owned(),
+                SpineAnimationFrames {
+                    duration: 0.2,
+                    frames,
+                },
+            )]),
+        };
+        assert_eq!(
+            frame_data.frame("walk", 0.15).unwrap().hitboxes,
+            [hitbox("b")]
+        );
+        assert_eq!(
+            frame_data.frame("walk", 5.).unwrap().hitboxes,
+            [hitbox("c")]
+        );
+        assert!(frame_data.frame("run", 0.).is_none());
+
+        let mut world = World::new();
+        let mut frame_data_assets = Assets::<SpineFrameData>::default();
+        let handle = frame_data_assets.add(frame_data);
+        world.insert_resource(frame_data_assets);
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", false)
+            .unwrap();
+        spine.skeleton.set_scale_x(2.);
+        spine.update(0.15, Physics::Update);
+        let entity = world.spawn((spine, SpineHitboxes::new(handle))).id();
+        world.run_system_once(spine_hitboxes).unwrap();
+
+        let active = world.get::<SpineHitboxes>(entity).unwrap().active();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].attachment, "b");
+        assert_eq!(active[0].polygon, [Vec2::new(2., 2.)]);
+    }
+}
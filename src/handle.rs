@@ -1,11 +1,11 @@
-use crate::{Crossfades, SkeletonData, SpineLoader, SpineSettings};
+use crate::{Crossfades, SkeletonData, SpineAnimationPlayer, SpineLoader, SpineSettings};
 use bevy::prelude::*;
 
 /// Attach this component to an entity to load and spawn a Spine skeleton.
 ///
 /// This component uses Bevy required components to automatically add
-/// [`SpineLoader`], [`SpineSettings`], [`Crossfades`], [`Transform`], and
-/// [`Visibility`] when they are not already present.
+/// [`SpineLoader`], [`SpineSettings`], [`Crossfades`], [`SpineAnimationPlayer`], [`Transform`],
+/// and [`Visibility`] when they are not already present.
 ///
 /// ```
 /// # use bevy::prelude::*;
@@ -18,7 +18,14 @@ use bevy::prelude::*;
 /// # }
 /// ```
 #[derive(Default, Component, Clone, Reflect)]
-#[require(SpineLoader, SpineSettings, Crossfades, Transform, Visibility)]
+#[require(
+    SpineLoader,
+    SpineSettings,
+    Crossfades,
+    SpineAnimationPlayer,
+    Transform,
+    Visibility
+)]
 #[reflect(Component, Default, Clone)]
 pub struct SkeletonDataHandle(pub Handle<SkeletonData>);
 
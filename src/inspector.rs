@@ -0,0 +1,282 @@
+//! A reflected view onto live Spine skeletons, for inspectors and editors.
+
+use bevy::prelude::*;
+
+use crate::{Spine, SpineBoneState, SpineTrackState};
+
+/// A reflected copy of the live state of a [`Spine`], for tools such as `bevy-inspector-egui` or
+/// the remote protocol, which cannot see into [`SkeletonController`](crate::SkeletonController).
+///
+/// Add this component to a Spine entity to refresh it after every
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation). Any change made to it
+/// (through reflection or otherwise) is written back into the skeleton before the next update.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineInspector;
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((SkeletonDataHandle::default(), SpineInspector::default()));
+/// # }
+///
+/// fn tint_red(mut inspector_query: Query<&mut SpineInspector>) {
+///     for mut inspector in inspector_query.iter_mut() {
+///         for slot in inspector.slots.iter_mut() {
+///             slot.color = Color::srgb(1., 0., 0.);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Bones keyed by a playing animation are posed by it again on every update, so edits to those
+/// bones only last a frame.
+#[derive(Component, Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineInspector {
+    pub skin: Option<String>,
+    /// The current entry of every track. Removing a track clears it.
+    pub tracks: Vec<SpineTrackState>,
+    /// Every slot, in setup draw order.
+    pub slots: Vec<SpineSlotState>,
+    /// The local pose of every bone, parents first.
+    pub bones: Vec<SpineBoneState>,
+}
+
+/// The color and attachment of a slot, see [`SpineInspector`].
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineSlotState {
+    pub slot: String,
+    pub color: Color,
+    pub attachment: Option<String>,
+}
+
+impl SpineInspector {
+    fn refresh(&mut self, spine: &Spine) {
+        let skeleton = &spine.skeleton;
+        self.skin = skeleton.skin().map(|skin| skin.name().to_owned());
+        self.tracks = spine
+            .animation_state
+            .tracks()
+            .enumerate()
+            .filter_map(|(track, track_entry)| {
+                let track_entry = track_entry?;
+                Some(SpineTrackState {
+                    track,
+                    animation: track_entry.animation().name().to_owned(),
+                    looping: track_entry.looping(),
+                    time: track_entry.track_time(),
+                    time_scale: track_entry.timescale(),
+                    alpha: track_entry.alpha(),
+                })
+            })
+            .collect();
+        self.slots = skeleton
+            .slots()
+            .map(|slot| {
+                let color = slot.color();
+                SpineSlotState {
+                    slot: slot.data().name().to_owned(),
+                    color: Color::linear_rgba(color.r, color.g, color.b, color.a),
+                    attachment: slot
+                        .attachment()
+                        .map(|attachment| attachment.name().to_owned()),
+                }
+            })
+            .collect();
+        self.bones = skeleton
+            .bones()
+            .map(|bone| SpineBoneState {
+                bone: bone.data().name().to_owned(),
+                x: bone.x(),
+                y: bone.y(),
+                rotation: bone.rotation(),
+                scale_x: bone.scale_x(),
+                scale_y: bone.scale_y(),
+            })
+            .collect();
+    }
+
+    /// Write this view back into the live skeleton.
+    fn write_back(&self, spine: &mut Spine) {
+        let Spine(controller) = spine;
+        let skin = controller
+            .skeleton
+            .skin()
+            .map(|skin| skin.name().to_owned());
+        if self.skin != skin
+            && let Some(skin) = &self.skin
+        {
+            if controller.skeleton.set_skin_by_name(skin).is_ok() {
+                controller.skeleton.set_slots_to_setup_pose();
+            } else {
+                warn!("cannot set unknown spine skin: {skin}");
+            }
+        }
+
+        let track_count = controller.animation_state.tracks().count();
+        for track in 0..track_count {
+            if controller.animation_state.track_at_index(track).is_some()
+                && !self
+                    .tracks
+                    .iter()
+                    .any(|track_state| track_state.track == track)
+            {
+                controller.animation_state.clear_track(track);
+            }
+        }
+        for track_state in self.tracks.iter() {
+            let playing = controller
+                .animation_state
+                .track_at_index(track_state.track)
+                .is_some_and(|track_entry| track_entry.animation().name() == track_state.animation);
+            if !playing
+                && controller
+                    .animation_state
+                    .set_animation_by_name(
+                        track_state.track,
+                        &track_state.animation,
+                        track_state.looping,
+                    )
+                    .is_err()
+            {
+                warn!(
+                    "cannot set unknown spine animation: {}",
+                    track_state.animation
+                );
+                continue;
+            }
+            let Some(mut track_entry) = controller
+                .animation_state
+                .track_at_index_mut(track_state.track)
+            else {
+                continue;
+            };
+            if track_entry.looping() != track_state.looping {
+                track_entry.set_looping(track_state.looping);
+            }
+            if track_entry.track_time() != track_state.time {
+                track_entry.set_track_time(track_state.time);
+            }
+            if track_entry.timescale() != track_state.time_scale {
+                track_entry.set_timescale(track_state.time_scale);
+            }
+            if track_entry.alpha() != track_state.alpha {
+                track_entry.set_alpha(track_state.alpha);
+            }
+        }
+
+        for slot_state in self.slots.iter() {
+            let attachment = {
+                let Some(mut slot) = controller.skeleton.find_slot_mut(&slot_state.slot) else {
+                    continue;
+                };
+                let [r, g, b, a] = slot_state.color.to_linear().to_f32_array();
+                *slot.color_mut() = rusty_spine::Color::new_rgba(r, g, b, a);
+                slot.attachment()
+                    .map(|attachment| attachment.name().to_owned())
+            };
+            if attachment != slot_state.attachment
+                && !controller
+                    .skeleton
+                    .set_attachment(&slot_state.slot, slot_state.attachment.as_deref())
+            {
+                warn!(
+                    "cannot set unknown spine attachment: {} on slot {}",
+                    slot_state.attachment.as_deref().unwrap_or("none"),
+                    slot_state.slot
+                );
+            }
+        }
+
+        for bone_state in self.bones.iter() {
+            let Some(mut bone) = controller.skeleton.find_bone_mut(&bone_state.bone) else {
+                continue;
+            };
+            bone.set_x(bone_state.x);
+            bone.set_y(bone_state.y);
+            bone.set_rotation(bone_state.rotation);
+            bone.set_scale_x(bone_state.scale_x);
+            bone.set_scale_y(bone_state.scale_y);
+        }
+    }
+}
+
+pub(crate) fn spine_inspector_write_back(
+    mut inspector_query: Query<(&mut Spine, Ref<SpineInspector>)>,
+) {
+    for (mut spine, inspector) in inspector_query.iter_mut() {
+        // the inspector is refreshed without triggering change detection, so any change came from
+        // outside
+        if inspector.is_changed() && !inspector.is_added() {
+            inspector.write_back(&mut spine);
+        }
+    }
+}
+
+pub(crate) fn spine_inspector_refresh(mut inspector_query: Query<(&Spine, &mut SpineInspector)>) {
+    for (spine, mut inspector) in inspector_query.iter_mut() {
+        inspector.bypass_change_detection().refresh(spine);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{SpineInspector, spine_inspector_refresh, spine_inspector_write_back};
+    use crate::{Spine, test_support::spineboy};
+
+    #[test]
+    fn write_back_changes() {
+        let mut app = App::new();
+        app.add_systems(
+            Update,
+            (spine_inspector_write_back, spine_inspector_refresh).chain(),
+        );
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        let entity = app
+            .world_mut()
+            .spawn((spine, SpineInspector::default()))
+            .id();
+        app.update();
+        let inspector = app.world().get::<SpineInspector>(entity).unwrap();
+        assert_eq!(inspector.tracks[0].animation, "walk");
+
+        // refreshing alone does not write back
+        app.world_mut()
+            .get_mut::<Spine>(entity)
+            .unwrap()
+            .animation_state
+            .track_at_index_mut(0)
+            .unwrap()
+            .set_track_time(0.5);
+        app.update();
+        let inspector = app.world().get::<SpineInspector>(entity).unwrap();
+        assert_eq!(inspector.tracks[0].time, 0.5);
+
+        let mut inspector = app.world_mut().get_mut::<SpineInspector>(entity).unwrap();
+        inspector.tracks[0].animation = "run".to_owned();
+        inspector.tracks[0].time_scale = 2.;
+        let slot = inspector
+            .slots
+            .iter_mut()
+            .find(|slot| slot.slot == "head-bb")
+            .unwrap();
+        slot.attachment = Some("head".to_owned());
+        slot.color = Color::linear_rgba(1., 0., 0., 1.);
+        app.update();
+
+        let spine = app.world().get::<Spine>(entity).unwrap();
+        let track_entry = spine.animation_state.track_at_index(0).unwrap();
+        assert_eq!(track_entry.animation().name(), "run");
+        assert_eq!(track_entry.timescale(), 2.);
+        let slot = spine.skeleton.find_slot("head-bb").unwrap();
+        assert_eq!(slot.attachment().unwrap().name(), "head");
+        assert_eq!(slot.color(), rusty_spine::Color::new_rgba(1., 0., 0., 1.));
+    }
+}
@@ -3,9 +3,15 @@
 //! Add [`SpinePlugin`] to your Bevy app and spawn a [`SkeletonDataHandle`] to get started!
 
 use std::{
-    collections::{HashMap, VecDeque},
+    borrow::Borrow,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
     mem::take,
-    sync::{Arc, Mutex},
+    ops::Deref,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
 use bevy::{
@@ -23,26 +29,52 @@ use materials::{
     SpineScreenPmaMaterial,
 };
 use rusty_spine::{
-    AnimationEvent, Physics, Skeleton,
+    AnimationEvent, Physics, Skeleton, TrackEntry,
     atlas::{AtlasFilter, AtlasWrap},
+    c_interface::NewFromPtr,
     controller::{SkeletonCombinedRenderable, SkeletonRenderable},
 };
 use textures::SpineTextureConfig;
 
 use crate::{
-    assets::{AtlasLoader, SkeletonJsonLoader},
+    animation_player::spine_animation_player,
+    assets::{AtlasLoader, RonAssetLoader, SkeletonJsonLoader},
+    attachment_override::{SpineImageTextures, spine_attachment_overrides},
+    attachment_watch::spine_attachment_watch,
+    blend_space::spine_blend_space,
+    bone_follower::spine_bone_followers,
+    crossfades::{SpineCrossfadesState, spine_crossfades},
+    entity_events::trigger_spine_event,
+    frame_data::spine_hitboxes,
+    inspector::{spine_inspector_refresh, spine_inspector_write_back},
+    lod::{SpineLodState, spine_lod},
     materials::{DARK_COLOR_ATTRIBUTE, SHADER_HANDLE, SpineMaterialPlugin},
+    mix_table::{SpineAnimationStateDataCache, spine_mix_table_changed},
+    root_motion::spine_root_motion,
     rusty_spine::{
-        AnimationStateData, BoneHandle, controller::SkeletonControllerSettings, draw::CullDirection,
+        AnimationState, AnimationStateData, BoneHandle, controller::SkeletonControllerSettings,
+        draw::CullDirection,
     },
+    scene::{spine_state_apply, spine_state_reload},
+    skins::{SpineSkinCache, spine_skins},
+    slot_overrides::{spine_slot_overrides_apply, spine_slot_overrides_restore},
+    slot_sync::{SpineSlotSyncPlugin, spine_spawn_slots},
+    state_machine::spine_state_machine,
     textures::{SpineTexture, SpineTextureCreateEvent, SpineTextureDisposeEvent, SpineTextures},
 };
 
-pub use crate::{assets::*, crossfades::Crossfades, entity_sync::*, handle::*, rusty_spine::Color};
+pub use crate::{
+    animation_player::*, assets::*, attachment_override::*, attachment_watch::*, blend_space::*,
+    bone_follower::*, crossfades::Crossfades, entity_events::*, entity_sync::*, frame_data::*,
+    handle::*, inspector::*, lod::*, mix_table::*, root_motion::*, rusty_spine::Color, scene::*,
+    skeleton_info::*, skins::*, slot_overrides::*, slot_sync::*, snapshot::*, state_machine::*,
+};
 
 /// See [`rusty_spine`] docs for more info.
 pub use crate::rusty_spine::controller::SkeletonController;
 
+#[cfg(feature = "audio")]
+pub use crate::audio::*;
 #[cfg(feature = "ui")]
 pub use crate::ui::*;
 
@@ -124,12 +156,17 @@ impl Plugin for SpinePlugin {
             SpineMaterialPlugin::<SpineMultiplyPmaMaterial>::default(),
             SpineMaterialPlugin::<SpineScreenPmaMaterial>::default(),
         ))
-        .add_plugins(SpineSyncPlugin::first())
+        .add_plugins((SpineSyncPlugin::first(), SpineSlotSyncPlugin::first()))
         .register_type::<Crossfades>()
         .register_type::<SkeletonDataHandle>()
         .register_type::<SpineSync>()
+        .register_type::<SpineSyncMode>()
+        .register_type::<SpineBoneFollower>()
         .register_type::<Spine>()
         .register_type::<SpineBone>()
+        .register_type::<SpineSlots>()
+        .register_type::<SpineSlot>()
+        .register_type::<SpineSlotSync>()
         .register_type::<SpineMeshes>()
         .register_type::<SpineMesh>()
         .register_type::<SpineMeshState>()
@@ -137,7 +174,38 @@ impl Plugin for SpinePlugin {
         .register_type::<SpineSettings>()
         .register_type::<SpineMeshType>()
         .register_type::<SpineDrawer>()
+        .register_type::<SpineLod>()
+        .register_type::<SpineLodLevel>()
+        .register_type::<SpineUpdateRate>()
+        .register_type::<SpineDefaultLod>()
+        .register_type::<SpineStateMachine>()
+        .register_type::<SpineBlendSpace>()
+        .register_type::<SpineBlendSpaceKind>()
+        .register_type::<SpineBlendPoint>()
+        .register_type::<SpineAnimationPlayer>()
+        .register_type::<SpinePlayback>()
+        .register_type::<SpinePlay>()
+        .register_type::<SpineSeek>()
+        .register_type::<SpinePaused>()
+        .register_type::<SpineHitboxes>()
+        .register_type::<SpineState>()
+        .register_type::<SpineInspector>()
+        .register_type::<SpineSkins>()
+        .register_type::<SpineAttachmentOverride>()
+        .register_type::<SpineAttachmentSource>()
+        .register_type::<SpineAttachmentWatch>()
+        .register_type::<SpineSlotOverrides>()
+        .register_type::<SpineDrawOrderMove>()
+        .register_type::<SpineDrawOrderPlacement>()
+        .register_type::<SpineMixTableHandle>()
+        .register_type::<SpineRootMotion>()
+        .register_type::<SpineRootMotionMode>()
         .init_resource::<SpineEventQueue>()
+        .init_resource::<SpineNamesCache>()
+        .init_resource::<SpineAnimationStateDataCache>()
+        .init_resource::<SpineSkinCache>()
+        .init_resource::<SpineImageTextures>()
+        .init_resource::<SpineDefaultLod>()
         .insert_resource(SpineTextures::init())
         .insert_resource(SpineReadyEvents::default())
         .add_message::<SpineTextureCreateEvent>()
@@ -146,39 +214,135 @@ impl Plugin for SpinePlugin {
         .init_asset::<SkeletonJson>()
         .init_asset::<SkeletonBinary>()
         .init_asset::<SkeletonData>()
+        .init_asset::<AnimationStateMachine>()
+        .init_asset::<SpineMixTable>()
+        .init_asset::<SpineFrameData>()
         .register_asset_reflect::<Atlas>()
         .register_asset_reflect::<SkeletonJson>()
         .register_asset_reflect::<SkeletonBinary>()
         .register_asset_reflect::<SkeletonData>()
+        .register_asset_reflect::<AnimationStateMachine>()
+        .register_asset_reflect::<SpineMixTable>()
+        .register_asset_reflect::<SpineFrameData>()
         .init_asset_loader::<AtlasLoader>()
         .init_asset_loader::<SkeletonJsonLoader>()
         .init_asset_loader::<SkeletonBinaryLoader>()
+        .init_asset_loader::<RonAssetLoader<AnimationStateMachine>>()
+        .init_asset_loader::<RonAssetLoader<SpineMixTable>>()
+        .init_asset_loader::<RonAssetLoader<SpineFrameData>>()
         .add_message::<SpineReadyEvent>()
         .add_message::<SpineEvent>()
+        .add_message::<SpineAttachmentChanged>()
         .add_systems(
             Update,
             (
                 spine_load.in_set(SpineSystem::Load),
+                spine_mix_table_changed
+                    .in_set(SpineSystem::Spawn)
+                    .before(spine_spawn),
+                spine_state_reload
+                    .in_set(SpineSystem::Spawn)
+                    .before(spine_spawn),
                 spine_spawn
                     .in_set(SpineSystem::Spawn)
                     .after(SpineSystem::Load),
+                spine_state_apply
+                    .in_set(SpineSystem::Ready)
+                    .after(SpineSystem::Spawn)
+                    .before(spine_ready),
+                spine_spawn_slots
+                    .in_set(SpineSystem::Ready)
+                    .after(SpineSystem::Spawn)
+                    .before(spine_ready),
                 spine_ready
                     .in_set(SpineSystem::Ready)
                     .after(SpineSystem::Spawn)
                     .before(SpineSet::OnReady),
+                ApplyDeferred
+                    .in_set(SpineSystem::SpawnFlush)
+                    .after(SpineSystem::Spawn)
+                    .before(SpineSystem::Ready),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                spine_blend_space
+                    .after(SpineSet::OnReady)
+                    .before(SpineSystem::UpdateAnimation),
+                spine_skins
+                    .after(SpineSet::OnReady)
+                    .before(SpineSystem::UpdateAnimation),
+                spine_animation_player
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnReady)
+                    .before(spine_update_animation),
+                spine_crossfades
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnReady)
+                    .before(spine_animation_player)
+                    .before(spine_state_machine)
+                    .before(spine_update_animation),
+                spine_state_machine
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnReady)
+                    .before(spine_update_animation),
+                spine_inspector_write_back
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnReady)
+                    .before(spine_animation_player)
+                    .before(spine_state_machine)
+                    .before(spine_update_animation),
+                spine_lod
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnReady)
+                    .before(spine_update_animation),
                 spine_update_animation
                     .in_set(SpineSystem::UpdateAnimation)
                     .after(SpineSet::OnReady)
                     .before(SpineSet::OnEvent),
+                spine_root_motion
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(spine_update_animation)
+                    .before(SpineSet::OnEvent),
+                spine_hitboxes
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(spine_update_animation)
+                    .before(SpineSet::OnEvent),
+                spine_attachment_overrides
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(spine_update_animation)
+                    .before(SpineSet::OnEvent),
+                spine_attachment_watch
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(spine_update_animation)
+                    .after(spine_attachment_overrides)
+                    .before(SpineSet::OnEvent),
+                spine_inspector_refresh
+                    .in_set(SpineSystem::UpdateAnimation)
+                    .after(spine_update_animation)
+                    .after(spine_root_motion)
+                    .before(SpineSet::OnEvent),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
                 spine_update_meshes
                     .in_set(SpineSystem::UpdateMeshes)
                     .in_set(SpineSet::OnUpdateMesh)
                     .after(SpineSystem::UpdateAnimation)
                     .after(SpineSet::OnEvent),
-                ApplyDeferred
-                    .in_set(SpineSystem::SpawnFlush)
-                    .after(SpineSystem::Spawn)
-                    .before(SpineSystem::Ready),
+                spine_slot_overrides_apply
+                    .after(SpineSystem::UpdateAnimation)
+                    .after(SpineSet::OnEvent)
+                    .after(SpineSyncSystem::SyncEntitiesApplied)
+                    .after(SpineSlotSyncSystem::SyncSlots)
+                    .before(SpineSystem::UpdateMeshes),
+                spine_bone_followers
+                    .after(SpineSystem::UpdateAnimation)
+                    .after(SpineSyncSystem::SyncEntitiesApplied),
+                spine_slot_overrides_restore.after(SpineSystem::UpdateMeshes),
             ),
         )
         .add_systems(
@@ -186,6 +350,8 @@ impl Plugin for SpinePlugin {
             adjust_spine_textures.in_set(SpineSystem::AdjustSpineTextures),
         );
 
+        #[cfg(feature = "audio")]
+        app.add_plugins(audio::SpineAudioPlugin);
         #[cfg(feature = "ui")]
         app.add_plugins(ui::SpineUiPlugin);
 
@@ -201,6 +367,8 @@ impl Plugin for SpinePlugin {
     }
 }
 
+/// Events captured by Spine listeners. The queue is drained every frame, keeping its capacity so
+/// that queueing events does not allocate.
 #[derive(Resource, Default)]
 struct SpineEventQueue(Arc<Mutex<VecDeque<SpineEvent>>>);
 
@@ -209,7 +377,11 @@ struct SpineEventQueue(Arc<Mutex<VecDeque<SpineEvent>>>);
 /// This component does not exist immediately when an entity is spawned with
 /// [`SkeletonDataHandle`], since Spine assets may not yet be loaded. Querying for this component
 /// type guarantees that all entities containing it have a Spine rig that is ready to use.
+///
+/// Skeletons are updated every frame, unless a [`SpineLod`] (or [`SpineDefaultLod`]) reduces the
+/// update rate.
 #[derive(Component, Debug, Reflect)]
+#[require(SpineLodState)]
 #[reflect(Component, Debug, from_reflect = false)]
 pub struct Spine(#[reflect(ignore)] pub SkeletonController);
 
@@ -425,7 +597,8 @@ pub struct SpineReadyEvent {
 
 /// A Spine event fired from a playing animation.
 ///
-/// Sent in [`SpineSystem::UpdateAnimation`].
+/// Sent in [`SpineSystem::UpdateAnimation`]. Each event is also triggered on the Spine entity as a
+/// typed [`EntityEvent`] for observers (see [`SpineUserEvent`] and [`SpineAnimationStart`]).
 ///
 /// ```
 /// # use bevy::prelude::*;
@@ -437,9 +610,10 @@ pub struct SpineReadyEvent {
 ///     asset_server: Res<AssetServer>,
 /// ) {
 ///     for event in spine_events.read() {
-///         if let SpineEvent::Event { name, entity, .. } = event {
+///         if let SpineEvent::Event { name, entity, track, time, .. } = event {
 ///             println!("spine event fired: {}", name);
 ///             println!("from entity: {:?}", entity);
+///             println!("on track {} at {}s", track.track_index, time);
 ///         }
 ///     }
 /// }
@@ -448,35 +622,331 @@ pub struct SpineReadyEvent {
 pub enum SpineEvent {
     Start {
         entity: Entity,
-        animation: String,
+        animation: SpineName,
+        track: SpineTrackInfo,
     },
     Interrupt {
         entity: Entity,
-        animation: String,
+        animation: SpineName,
+        track: SpineTrackInfo,
     },
     End {
         entity: Entity,
-        animation: String,
+        animation: SpineName,
+        track: SpineTrackInfo,
     },
     Complete {
         entity: Entity,
-        animation: String,
+        animation: SpineName,
+        track: SpineTrackInfo,
     },
     Dispose {
         entity: Entity,
+        animation: SpineName,
+        track: SpineTrackInfo,
     },
     Event {
         entity: Entity,
-        name: String,
+        animation: SpineName,
+        track: SpineTrackInfo,
+        name: SpineName,
+        /// The time this event is keyed at in the animation.
+        time: f32,
+        /// The time in the animation when this event fired, which may be later than `time` if the
+        /// skeleton was updated with a large delta.
+        animation_time: f32,
         int: i32,
         float: f32,
-        string: String,
-        audio_path: String,
+        string: SpineName,
+        audio_path: SpineName,
         volume: f32,
         balance: f32,
     },
 }
 
+impl SpineEvent {
+    /// The entity containing the [`Spine`] component this event was fired from.
+    pub fn entity(&self) -> Entity {
+        match self {
+            SpineEvent::Start { entity, .. }
+            | SpineEvent::Interrupt { entity, .. }
+            | SpineEvent::End { entity, .. }
+            | SpineEvent::Complete { entity, .. }
+            | SpineEvent::Dispose { entity, .. }
+            | SpineEvent::Event { entity, .. } => *entity,
+        }
+    }
+
+    /// The track entry this event was fired from.
+    pub fn track(&self) -> &SpineTrackInfo {
+        match self {
+            SpineEvent::Start { track, .. }
+            | SpineEvent::Interrupt { track, .. }
+            | SpineEvent::End { track, .. }
+            | SpineEvent::Complete { track, .. }
+            | SpineEvent::Dispose { track, .. }
+            | SpineEvent::Event { track, .. } => track,
+        }
+    }
+}
+
+/// The state of the track entry a [`SpineEvent`] was fired from, at the time it fired.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpineTrackInfo {
+    /// Identifies the track entry for as long as it lives, from [`SpineEvent::Start`] to
+    /// [`SpineEvent::Dispose`]. Useful to tell apart the same animation playing on several tracks,
+    /// or played again on the same track.
+    pub id: SpineTrackEntryId,
+    pub track_index: usize,
+    pub track_time: f32,
+    pub animation_duration: f32,
+    /// The number of loops completed by this track entry, counted like Spine does from the
+    /// track time and the entry's animation start and end. Always `0` if the entry does not loop.
+    pub loop_count: u32,
+}
+
+/// A unique identifier for a track entry, see [`SpineTrackInfo::id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpineTrackEntryId(pub u64);
+
+/// Assigns [`SpineTrackEntryId`]s to live track entries, by address.
+#[derive(Default)]
+struct SpineTrackEntryIds(HashMap<usize, SpineTrackEntryId>);
+
+impl SpineTrackEntryIds {
+    fn info(&mut self, track_entry: &TrackEntry) -> SpineTrackInfo {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = *self
+            .0
+            .entry(track_entry.c_ptr() as usize)
+            .or_insert_with(|| SpineTrackEntryId(NEXT_ID.fetch_add(1, Ordering::Relaxed)));
+        let track_time = track_entry.track_time();
+        let animation_duration = track_entry.animation().duration();
+        let loop_duration = track_entry.animation_end() - track_entry.animation_start();
+        SpineTrackInfo {
+            id,
+            track_index: track_entry.track_index(),
+            track_time,
+            animation_duration,
+            loop_count: if track_entry.looping() && loop_duration > 0. {
+                (track_time / loop_duration) as u32
+            } else {
+                0
+            },
+        }
+    }
+
+    fn dispose(&mut self, track_entry: &TrackEntry) {
+        self.0.remove(&(track_entry.c_ptr() as usize));
+    }
+}
+
+/// An interned animation or event name, as carried by [`SpineEvent`].
+///
+/// Names are interned per [`SkeletonData`], so events can be sent without allocating. Cloning is
+/// cheap, and names compare directly against strings:
+///
+/// ```
+/// # use bevy_spine::SpineName;
+/// let name = SpineName::from("footstep");
+/// assert!(name == "footstep");
+/// assert_eq!(name.as_str(), "footstep");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpineName(Arc<str>);
+
+impl SpineName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for SpineName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SpineName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SpineName {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SpineName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for SpineName {
+    fn from(name: &str) -> Self {
+        Self(Arc::from(name))
+    }
+}
+
+impl From<String> for SpineName {
+    fn from(name: String) -> Self {
+        Self(Arc::from(name))
+    }
+}
+
+impl PartialEq<str> for SpineName {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SpineName {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SpineName {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+/// The animation and event names of a skeleton, see [`SpineName`].
+struct SpineNames(HashSet<SpineName>);
+
+impl SpineNames {
+    fn new(skeleton_data: &rusty_spine::SkeletonData) -> Self {
+        let mut names = HashSet::new();
+        for animation in skeleton_data.animations() {
+            names.insert(SpineName::from(animation.name()));
+        }
+        for index in 0..skeleton_data.events_count() {
+            // SAFETY: `events` holds `eventsCount` event data owned by the skeleton data, which
+            // outlives this loop.
+            let event_data = unsafe {
+                rusty_spine::EventData::new_from_ptr(*(*skeleton_data.c_ptr()).events.add(index))
+            };
+            names.insert(SpineName::from(event_data.name()));
+            names.insert(SpineName::from(event_data.string_value()));
+            names.insert(SpineName::from(event_data.audio_path()));
+        }
+        Self(names)
+    }
+
+    /// The interned name, only allocating if it is not part of the skeleton data (for example a
+    /// string overridden on an event key).
+    fn get(&self, name: &str) -> SpineName {
+        self.0
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| SpineName::from(name))
+    }
+}
+
+/// [`SpineNames`] shared by every Spine spawned from the same [`SkeletonData`].
+#[derive(Default, Resource)]
+struct SpineNamesCache(
+    HashMap<AssetId<SkeletonData>, (Weak<rusty_spine::SkeletonData>, Arc<SpineNames>)>,
+);
+
+impl SpineNamesCache {
+    fn get(
+        &mut self,
+        id: AssetId<SkeletonData>,
+        skeleton_data: &Arc<rusty_spine::SkeletonData>,
+    ) -> Arc<SpineNames> {
+        if let Some((cached_skeleton_data, names)) = self.0.get(&id)
+            && cached_skeleton_data.ptr_eq(&Arc::downgrade(skeleton_data))
+        {
+            return names.clone();
+        }
+        let names = Arc::new(SpineNames::new(skeleton_data));
+        self.0
+            .insert(id, (Arc::downgrade(skeleton_data), names.clone()));
+        names
+    }
+}
+
+/// Queue the events of `animation_state` as [`SpineEvent`]s of `spine_entity`.
+///
+/// Events fired while [`Spine::restore`] replaces the tracks are dropped.
+fn set_spine_event_listener(
+    animation_state: &mut AnimationState,
+    spine_entity: Entity,
+    events: Arc<Mutex<VecDeque<SpineEvent>>>,
+    names: Arc<SpineNames>,
+) {
+    let track_entry_ids = Mutex::new(SpineTrackEntryIds::default());
+    animation_state.set_listener(move |_, animation_event| {
+        let mut track_entry_ids = track_entry_ids.lock().unwrap();
+        let event = match animation_event {
+            AnimationEvent::Start { track_entry } => SpineEvent::Start {
+                entity: spine_entity,
+                animation: names.get(track_entry.animation().name()),
+                track: track_entry_ids.info(&track_entry),
+            },
+            AnimationEvent::Interrupt { track_entry } => SpineEvent::Interrupt {
+                entity: spine_entity,
+                animation: names.get(track_entry.animation().name()),
+                track: track_entry_ids.info(&track_entry),
+            },
+            AnimationEvent::End { track_entry } => SpineEvent::End {
+                entity: spine_entity,
+                animation: names.get(track_entry.animation().name()),
+                track: track_entry_ids.info(&track_entry),
+            },
+            AnimationEvent::Complete { track_entry } => SpineEvent::Complete {
+                entity: spine_entity,
+                animation: names.get(track_entry.animation().name()),
+                track: track_entry_ids.info(&track_entry),
+            },
+            AnimationEvent::Dispose { track_entry } => {
+                let track = track_entry_ids.info(&track_entry);
+                track_entry_ids.dispose(&track_entry);
+                SpineEvent::Dispose {
+                    entity: spine_entity,
+                    animation: names.get(track_entry.animation().name()),
+                    track,
+                }
+            }
+            AnimationEvent::Event {
+                track_entry,
+                name,
+                time,
+                int,
+                float,
+                string,
+                audio_path,
+                volume,
+                balance,
+                ..
+            } => SpineEvent::Event {
+                entity: spine_entity,
+                animation: names.get(track_entry.animation().name()),
+                track: track_entry_ids.info(&track_entry),
+                name: names.get(name),
+                time,
+                animation_time: track_entry.animation_time(),
+                int,
+                float,
+                string: names.get(string),
+                audio_path: names.get(audio_path),
+                volume,
+                balance,
+            },
+        };
+        if !snapshot::restoring() {
+            events.lock().unwrap().push_back(event);
+        }
+    });
+}
+
 /// Queued ready events, to be sent after [`SpineSystem::SpawnFlush`].
 #[derive(Default, Resource)]
 struct SpineReadyEvents(Vec<SpineReadyEvent>);
@@ -566,24 +1036,38 @@ fn spine_load(
     );
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn spine_spawn(
     mut skeleton_query: Query<(
         &mut SpineLoader,
         Entity,
         &SkeletonDataHandle,
         Option<&Crossfades>,
+        Option<&SpineMixTableHandle>,
         Option<&RenderLayers>,
         Option<&SpineRenderOwner>,
+        Option<&SpineSyncMode>,
     )>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut ready_events: ResMut<SpineReadyEvents>,
     mut skeleton_data_assets: ResMut<Assets<SkeletonData>>,
+    mut names_cache: ResMut<SpineNamesCache>,
+    mut animation_state_data_cache: ResMut<SpineAnimationStateDataCache>,
+    mix_tables: Res<Assets<SpineMixTable>>,
+    asset_server: Res<AssetServer>,
     spine_event_queue: Res<SpineEventQueue>,
 ) {
-    for (mut spine_loader, spine_entity, data_handle, crossfades, render_layers, render_owner) in
-        skeleton_query.iter_mut()
+    for (
+        mut spine_loader,
+        spine_entity,
+        data_handle,
+        crossfades,
+        mix_table_handle,
+        render_layers,
+        render_owner,
+        sync_mode,
+    ) in skeleton_query.iter_mut()
     {
         if let SpineLoader::Loading { with_children } = spine_loader.as_ref() {
             let skeleton_data_asset =
@@ -592,83 +1076,70 @@ fn spine_spawn(
                 } else {
                     continue;
                 };
+            let mix_table = match mix_table_handle {
+                Some(mix_table_handle) => match mix_tables.get(&mix_table_handle.0) {
+                    Some(mix_table) => Some((mix_table_handle.0.id(), mix_table)),
+                    None if asset_server.load_state(&mix_table_handle.0).is_failed() => None,
+                    None => continue,
+                },
+                None => None,
+            };
             match &skeleton_data_asset.status {
                 SkeletonDataStatus::Loaded(skeleton_data) => {
-                    let mut animation_state_data = AnimationStateData::new(skeleton_data.clone());
-                    if let Some(crossfades) = crossfades {
-                        crossfades.apply(&mut animation_state_data);
-                    }
-                    let mut controller = SkeletonController::new(
-                        skeleton_data.clone(),
-                        Arc::new(animation_state_data),
-                    )
-                    .with_settings(
-                        SkeletonControllerSettings::new()
-                            .with_cull_direction(CullDirection::CounterClockwise)
-                            .with_premultiplied_alpha(skeleton_data_asset.premultiplied_alpha),
+                    let (animation_state_data, crossfades_state) = match mix_table {
+                        Some((mix_table_id, mix_table))
+                            if crossfades.is_none_or(Crossfades::is_empty) =>
+                        {
+                            (
+                                animation_state_data_cache.get(
+                                    (data_handle.0.id(), mix_table_id),
+                                    skeleton_data,
+                                    mix_table,
+                                ),
+                                SpineCrossfadesState {
+                                    applied: HashSet::new(),
+                                    shared: true,
+                                },
+                            )
+                        }
+                        _ => {
+                            let mut animation_state_data =
+                                AnimationStateData::new(skeleton_data.clone());
+                            let applied = SpineMixTable::layer(
+                                mix_table.map(|(_, mix_table)| mix_table),
+                                crossfades,
+                            )
+                            .apply(&mut animation_state_data, skeleton_data);
+                            (
+                                Arc::new(animation_state_data),
+                                SpineCrossfadesState {
+                                    applied,
+                                    shared: false,
+                                },
+                            )
+                        }
+                    };
+                    let mut controller =
+                        SkeletonController::new(skeleton_data.clone(), animation_state_data)
+                            .with_settings(
+                                SkeletonControllerSettings::new()
+                                    .with_cull_direction(CullDirection::CounterClockwise)
+                                    .with_premultiplied_alpha(
+                                        skeleton_data_asset.premultiplied_alpha,
+                                    ),
+                            );
+                    set_spine_event_listener(
+                        &mut controller.animation_state,
+                        spine_entity,
+                        spine_event_queue.0.clone(),
+                        names_cache.get(data_handle.0.id(), skeleton_data),
                     );
-                    let events = spine_event_queue.0.clone();
-                    controller
-                        .animation_state
-                        .set_listener(move |_, animation_event| match animation_event {
-                            AnimationEvent::Start { track_entry } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::Start {
-                                    entity: spine_entity,
-                                    animation: track_entry.animation().name().to_owned(),
-                                });
-                            }
-                            AnimationEvent::Interrupt { track_entry } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::Interrupt {
-                                    entity: spine_entity,
-                                    animation: track_entry.animation().name().to_owned(),
-                                });
-                            }
-                            AnimationEvent::End { track_entry } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::End {
-                                    entity: spine_entity,
-                                    animation: track_entry.animation().name().to_owned(),
-                                });
-                            }
-                            AnimationEvent::Complete { track_entry } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::Complete {
-                                    entity: spine_entity,
-                                    animation: track_entry.animation().name().to_owned(),
-                                });
-                            }
-                            AnimationEvent::Dispose { .. } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::Dispose {
-                                    entity: spine_entity,
-                                });
-                            }
-                            AnimationEvent::Event {
-                                name,
-                                int,
-                                float,
-                                string,
-                                audio_path,
-                                volume,
-                                balance,
-                                ..
-                            } => {
-                                let mut events = events.lock().unwrap();
-                                events.push_back(SpineEvent::Event {
-                                    entity: spine_entity,
-                                    name: name.to_owned(),
-                                    int,
-                                    float,
-                                    string: string.to_owned(),
-                                    audio_path: audio_path.to_owned(),
-                                    volume,
-                                    balance,
-                                });
-                            }
-                        });
                     controller.skeleton.set_to_setup_pose();
+                    let world_bones = sync_mode == Some(&SpineSyncMode::World);
+                    if world_bones {
+                        // bone entities start from the setup pose world transforms
+                        controller.skeleton.update_world_transform(Physics::None);
+                    }
                     let mut bones = HashMap::new();
                     let render_layers = render_layers.cloned();
                     let render_owner = render_owner.copied();
@@ -742,11 +1213,12 @@ fn spine_spawn(
                                         controller.skeleton.bone_root().handle(),
                                         render_layers_for_children.as_ref(),
                                         render_owner.as_ref(),
+                                        world_bones,
                                         &mut bones,
                                     );
                                 }
                             })
-                            .insert(Spine(controller));
+                            .insert((Spine(controller), crossfades_state));
                     }
                     *spine_loader = SpineLoader::Ready;
                     ready_events.0.push(SpineReadyEvent {
@@ -772,16 +1244,23 @@ fn spawn_bones(
     bone: BoneHandle,
     render_layers: Option<&RenderLayers>,
     render_owner: Option<&SpineRenderOwner>,
+    world: bool,
     bones: &mut HashMap<String, Entity>,
 ) {
     if let Some(bone) = bone.get(skeleton) {
-        let mut transform = Transform::default();
-        transform.translation.x = bone.applied_x();
-        transform.translation.y = bone.applied_y();
-        transform.translation.z = 0.;
-        transform.rotation = Quat::from_axis_angle(Vec3::Z, bone.applied_rotation().to_radians());
-        transform.scale.x = bone.applied_scale_x();
-        transform.scale.y = bone.applied_scale_y();
+        let transform = if world {
+            bone_world_transform(&bone)
+        } else {
+            let mut transform = Transform::default();
+            transform.translation.x = bone.applied_x();
+            transform.translation.y = bone.applied_y();
+            transform.translation.z = 0.;
+            transform.rotation =
+                Quat::from_axis_angle(Vec3::Z, bone.applied_rotation().to_radians());
+            transform.scale.x = bone.applied_scale_x();
+            transform.scale.y = bone.applied_scale_y();
+            transform
+        };
         let mut bone_entity_commands = spawner.spawn((
             Name::new(format!("spine_bone ({})", bone.data().name())),
             transform,
@@ -798,31 +1277,49 @@ fn spawn_bones(
             bone_entity_commands.insert(*render_owner);
         }
 
-        let bone_entity = bone_entity_commands
-            .insert(SpineBone {
-                spine_entity,
-                handle: bone.handle(),
-                name: bone.data().name().to_owned(),
-                parent: bone_parent,
-            })
-            .with_children(|parent| {
+        bone_entity_commands.insert(SpineBone {
+            spine_entity,
+            handle: bone.handle(),
+            name: bone.data().name().to_owned(),
+            parent: bone_parent,
+        });
+        let bone_entity = bone_entity_commands.id();
+        let bone_parent = || SpineBoneParent {
+            entity: bone_entity,
+            handle: bone.handle(),
+        };
+        if world {
+            // world transforms are relative to the Spine entity, so every bone entity is its child
+            for child in bone.children() {
+                spawn_bones(
+                    spine_entity,
+                    Some(bone_parent()),
+                    spawner,
+                    skeleton,
+                    child.handle(),
+                    render_layers,
+                    render_owner,
+                    world,
+                    bones,
+                );
+            }
+        } else {
+            bone_entity_commands.with_children(|parent| {
                 for child in bone.children() {
                     spawn_bones(
                         spine_entity,
-                        Some(SpineBoneParent {
-                            entity: parent.target_entity(),
-                            handle: bone.handle(),
-                        }),
+                        Some(bone_parent()),
                         parent,
                         skeleton,
                         child.handle(),
                         render_layers,
                         render_owner,
+                        world,
                         bones,
                     );
                 }
-            })
-            .id();
+            });
+        }
         bones.insert(bone.data().name().to_owned(), bone_entity);
     }
 }
@@ -837,20 +1334,55 @@ fn spine_ready(
 }
 
 fn spine_update_animation(
-    mut spine_query: Query<(Entity, &mut Spine)>,
+    mut spine_query: Query<(Entity, &mut Spine, &mut SpineLodState, Has<SpinePaused>)>,
     mut spine_events: MessageWriter<SpineEvent>,
-    time: Res<Time>,
+    mut commands: Commands,
+    mut suppressed: Local<HashSet<Entity>>,
     spine_event_queue: Res<SpineEventQueue>,
 ) {
-    for (_, mut spine) in spine_query.iter_mut() {
-        spine.update(time.delta_secs(), Physics::Update);
+    for (entity, mut spine, mut lod_state, paused) in spine_query.iter_mut() {
+        let seeked = take(&mut lod_state.seeked);
+        lod_state.redraw = lod_state.update || paused || seeked;
+        lod_state.applied_delta = 0.;
+        lod_state.applied_seek = seeked;
+        if paused {
+            lod_state.pending_delta = 0.;
+            lod_state.update = seeked;
+        } else if seeked {
+            lod_state.update = true;
+        }
+        if !lod_state.update {
+            continue;
+        }
+        let delta = take(&mut lod_state.pending_delta);
+        // a seek sets the track times, which the time since the last update must not move past
+        let delta = if seeked { 0. } else { delta };
+        lod_state.applied_delta = delta;
+        lod_state.frames = 0;
+        // seeking jumps the pose, which physics should not react to
+        spine.update(
+            delta,
+            if seeked || lod_state.reset_physics {
+                Physics::Reset
+            } else {
+                Physics::Update
+            },
+        );
+        if take(&mut lod_state.suppress_events) {
+            suppressed.insert(entity);
+        }
     }
     {
         let mut events = spine_event_queue.0.lock().unwrap();
-        while let Some(event) = events.pop_front() {
+        for event in events.drain(..) {
+            if suppressed.contains(&event.entity()) {
+                continue;
+            }
+            trigger_spine_event(&mut commands, &event);
             spine_events.write(event);
         }
     }
+    suppressed.clear();
 }
 
 pub enum SkeletonRenderableKind {
@@ -858,9 +1390,14 @@ pub enum SkeletonRenderableKind {
     Combined(Vec<SkeletonCombinedRenderable>),
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn spine_update_meshes(
-    mut spine_query: Query<(&mut Spine, Option<&SpineSettings>, &InheritedVisibility)>,
+    mut spine_query: Query<(
+        &mut Spine,
+        Option<&SpineSettings>,
+        &InheritedVisibility,
+        &SpineLodState,
+    )>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut mesh_query: Query<(
         Entity,
@@ -874,6 +1411,7 @@ fn spine_update_meshes(
     mut commands: Commands,
     mut meshes_query: Query<(&ChildOf, &Children, &mut SpineMeshesUpdateState), With<SpineMeshes>>,
     asset_server: Res<AssetServer>,
+    image_textures: Res<SpineImageTextures>,
 ) {
     const CULLED_RECOVERY_INTERVAL_FRAMES: u32 = 60;
 
@@ -891,7 +1429,7 @@ fn spine_update_meshes(
     }
 
     for (meshes_parent, meshes_children, mut update_state) in meshes_query.iter_mut() {
-        let Ok((mut spine, spine_mesh_type, inherited_visibility)) =
+        let Ok((mut spine, spine_mesh_type, inherited_visibility, lod_state)) =
             spine_query.get_mut(meshes_parent.parent())
         else {
             continue;
@@ -901,6 +1439,11 @@ fn spine_update_meshes(
             continue;
         }
 
+        // the skeleton has not moved since its last update, see `SpineLod`
+        if !lod_state.redraw && update_state.initialized {
+            continue;
+        }
+
         let SpineSettings {
             mesh_type,
             drawer,
@@ -1081,7 +1624,9 @@ fn spine_update_meshes(
                     spine_mesh.state = SpineMeshState::Renderable {
                         info: SpineMaterialInfo {
                             slot_index,
-                            texture: asset_server.load(texture_path),
+                            texture: image_textures
+                                .get(&texture_path)
+                                .unwrap_or_else(|| asset_server.load(texture_path)),
                             blend_mode,
                             premultiplied_alpha,
                         },
@@ -1221,10 +1766,32 @@ fn adjust_spine_textures(
     }
 }
 
+mod animation_player;
 mod assets;
+mod attachment_override;
+mod attachment_watch;
+#[cfg(feature = "audio")]
+mod audio;
+mod blend_space;
+mod bone_follower;
 mod crossfades;
+mod entity_events;
 mod entity_sync;
+mod frame_data;
 mod handle;
+mod inspector;
+mod lod;
+mod mix_table;
+mod root_motion;
+mod scene;
+mod skeleton_info;
+mod skins;
+mod slot_overrides;
+mod slot_sync;
+mod snapshot;
+mod state_machine;
+#[cfg(test)]
+mod test_support;
 #[cfg(feature = "ui")]
 mod ui;
 
@@ -1234,10 +1801,17 @@ pub mod textures;
 #[doc(hidden)]
 pub mod prelude {
     pub use crate::{
-        Crossfades, SkeletonController, SkeletonData, SkeletonDataHandle, Spine, SpineBone,
-        SpineEvent, SpineLoader, SpineMesh, SpineMeshState, SpinePlugin, SpineReadyEvent, SpineSet,
-        SpineSettings, SpineSync, SpineSyncSet, SpineSyncSystem, SpineSystem,
+        Crossfades, SkeletonController, SkeletonData, SkeletonDataHandle, Spine,
+        SpineAnimationComplete, SpineAnimationEnd, SpineAnimationPlayer, SpineAnimationStart,
+        SpineBlendSpace, SpineBone, SpineBoneFollower, SpineEntityCommandsExt, SpineEvent,
+        SpineHitboxes, SpineLoader, SpineLod, SpineMesh, SpineMeshState, SpineMixTableHandle,
+        SpineName, SpinePaused, SpinePlugin, SpineReadyEvent, SpineRootMotion, SpineSet,
+        SpineSettings, SpineSkins, SpineSlot, SpineSlotOverrides, SpineSlotSync, SpineSlotSyncSet,
+        SpineStateMachine, SpineSync, SpineSyncSet, SpineSyncSystem, SpineSystem, SpineTrackInfo,
+        SpineUserEvent,
     };
+    #[cfg(feature = "audio")]
+    pub use crate::{SpineAudio, SpineAudioSettings};
     #[cfg(feature = "ui")]
     pub use crate::{SpineUiFit, SpineUiNode, SpineUiProxy, SpineUiReadyEvent, SpineUiSkeleton};
     pub use rusty_spine::{BoneHandle, SlotHandle};
@@ -0,0 +1,281 @@
+//! Animation level-of-detail for distant or off-screen Spine skeletons.
+
+use bevy::prelude::*;
+
+use crate::{Spine, SpineMesh, SpineMeshes};
+
+/// Reduces how often a [`Spine`] is animated based on its distance to the closest camera and on
+/// whether any of its meshes are visible.
+///
+/// Skipped frames are not lost: their delta time is accumulated and applied on the next update,
+/// so animations stay in sync and [`SpineEvent`](crate::SpineEvent)s still fire, only later.
+/// Meshes are not rebuilt on frames where the animation was skipped.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::{SpineLodLevel, SpineUpdateRate};
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineLod {
+///         levels: vec![
+///             SpineLodLevel::new(500., SpineUpdateRate::EveryFrame),
+///             SpineLodLevel::new(1500., SpineUpdateRate::EveryNthFrame(2)),
+///             SpineLodLevel::new(f32::INFINITY, SpineUpdateRate::PerSecond(10.)),
+///         ],
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+/// ```
+///
+/// To apply a policy to every Spine entity without a [`SpineLod`], see [`SpineDefaultLod`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineLod {
+    /// Distance based levels, ordered from nearest to farthest.
+    ///
+    /// The first level whose [`SpineLodLevel::max_distance`] is greater than the distance to the
+    /// closest active camera is used. Past the last level, the last level is used. If empty, the
+    /// skeleton updates every frame while visible.
+    pub levels: Vec<SpineLodLevel>,
+    /// The update rate used while every mesh of this Spine is culled.
+    pub invisible: SpineUpdateRate,
+    /// If more time than this has accumulated between two updates, physics constraints are reset
+    /// instead of simulating the whole gap at once.
+    pub max_physics_delta: f32,
+}
+
+impl Default for SpineLod {
+    fn default() -> Self {
+        Self {
+            levels: vec![],
+            invisible: SpineUpdateRate::EveryNthFrame(10),
+            max_physics_delta: 0.25,
+        }
+    }
+}
+
+impl SpineLod {
+    fn rate(&self, distance: f32, visible: bool) -> SpineUpdateRate {
+        if !visible {
+            return self.invisible;
+        }
+        self.levels
+            .iter()
+            .find(|level| distance < level.max_distance)
+            .or(self.levels.last())
+            .map(|level| level.rate)
+            .unwrap_or(SpineUpdateRate::EveryFrame)
+    }
+}
+
+/// A single distance level in [`SpineLod`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct SpineLodLevel {
+    /// Distance to the closest camera below which this level applies.
+    ///
+    /// For orthographic cameras, this is the distance on the XY plane multiplied by the
+    /// projection scale, so zooming out counts as moving away.
+    pub max_distance: f32,
+    pub rate: SpineUpdateRate,
+}
+
+impl SpineLodLevel {
+    pub fn new(max_distance: f32, rate: SpineUpdateRate) -> Self {
+        Self { max_distance, rate }
+    }
+}
+
+/// How often a Spine animation is updated, see [`SpineLod`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum SpineUpdateRate {
+    /// Update every frame.
+    EveryFrame,
+    /// Update once every `n` frames.
+    EveryNthFrame(u32),
+    /// Update at most this many times per second.
+    PerSecond(f32),
+}
+
+impl SpineUpdateRate {
+    fn should_update(&self, frames: u32, delta: f32) -> bool {
+        match *self {
+            SpineUpdateRate::EveryFrame => true,
+            SpineUpdateRate::EveryNthFrame(n) => frames >= n.max(1),
+            SpineUpdateRate::PerSecond(rate) => rate <= 0. || delta >= 1. / rate,
+        }
+    }
+}
+
+/// A [`SpineLod`] applied to every [`Spine`] entity which does not have its own.
+///
+/// Defaults to `None`, updating every skeleton every frame.
+#[derive(Resource, Default, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct SpineDefaultLod(pub Option<SpineLod>);
+
+/// Tracks the delta time accumulated since a [`Spine`] was last updated.
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct SpineLodState {
+    pub(crate) pending_delta: f32,
+    pub(crate) frames: u32,
+    /// Whether the animation is updated this frame.
+    pub(crate) update: bool,
+    /// Whether meshes are rebuilt this frame. Paused skeletons are not updated but still redrawn,
+    /// so changes made to them, for example through bone entities, are shown.
+    pub(crate) redraw: bool,
+    /// Reset physics constraints on the next update, instead of simulating a long gap at once.
+    pub(crate) reset_physics: bool,
+    /// Set by a [`SpineSeek`](crate::SpineSeek), forcing an update this frame.
+    pub(crate) seeked: bool,
+    /// The delta time the animation was updated by this frame, `0` if it was not updated.
+    pub(crate) applied_delta: f32,
+    /// Whether the update this frame was a seek, which jumps track times.
+    pub(crate) applied_seek: bool,
+    pub(crate) suppress_events: bool,
+    initialized: bool,
+}
+
+impl Default for SpineLodState {
+    fn default() -> Self {
+        Self {
+            pending_delta: 0.,
+            frames: 0,
+            update: true,
+            redraw: true,
+            reset_physics: false,
+            seeked: false,
+            applied_delta: 0.,
+            applied_seek: false,
+            suppress_events: false,
+            initialized: false,
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_lod(
+    mut spine_query: Query<
+        (
+            &mut SpineLodState,
+            &GlobalTransform,
+            Option<&SpineLod>,
+            Option<&Children>,
+        ),
+        With<Spine>,
+    >,
+    meshes_query: Query<&Children, With<SpineMeshes>>,
+    mesh_visibility_query: Query<&ViewVisibility, With<SpineMesh>>,
+    camera_query: Query<(&Camera, &GlobalTransform, Option<&Projection>)>,
+    default_lod: Res<SpineDefaultLod>,
+    time: Res<Time>,
+) {
+    for (mut state, global_transform, lod, children) in spine_query.iter_mut() {
+        state.pending_delta += time.delta_secs();
+        state.frames += 1;
+        let Some(lod) = lod.or(default_lod.0.as_ref()) else {
+            state.update = true;
+            state.reset_physics = false;
+            continue;
+        };
+
+        let visible = children.into_iter().flatten().any(|child| {
+            meshes_query.get(*child).is_ok_and(|meshes| {
+                meshes.iter().any(|mesh| {
+                    mesh_visibility_query
+                        .get(mesh)
+                        .is_ok_and(|visibility| visibility.get())
+                })
+            })
+        });
+        let position = global_transform.translation();
+        let distance = camera_query
+            .iter()
+            .filter(|(camera, ..)| camera.is_active)
+            .map(|(_, camera_transform, projection)| match projection {
+                Some(Projection::Orthographic(orthographic)) => {
+                    position
+                        .truncate()
+                        .distance(camera_transform.translation().truncate())
+                        * orthographic.scale
+                }
+                _ => position.distance(camera_transform.translation()),
+            })
+            .fold(f32::INFINITY, f32::min);
+
+        // visibility is unknown until the first render, so always update once
+        state.update = !state.initialized
+            || lod
+                .rate(distance, visible)
+                .should_update(state.frames, state.pending_delta);
+        state.reset_physics = state.pending_delta > lod.max_physics_delta && state.initialized;
+        state.initialized = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{prelude::*, time::TimeUpdateStrategy};
+    use rusty_spine::Physics;
+
+    use super::{SpineDefaultLod, SpineLod, SpineLodLevel, SpineUpdateRate, spine_lod};
+    use crate::{SpineEvent, SpineEventQueue, spine_update_animation, test_support::spineboy};
+
+    fn track_time(app: &App, entity: Entity) -> f32 {
+        app.world()
+            .get::<crate::Spine>(entity)
+            .unwrap()
+            .animation_state
+            .get_current(0)
+            .unwrap()
+            .track_time()
+    }
+
+    #[test]
+    fn skipped_deltas_accumulate() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<SpineDefaultLod>()
+            .init_resource::<SpineEventQueue>()
+            .add_message::<SpineEvent>()
+            .add_systems(Update, (spine_lod, spine_update_animation).chain());
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0., Physics::Update);
+        let every_third = SpineUpdateRate::EveryNthFrame(3);
+        let entity = app
+            .world_mut()
+            .spawn((
+                spine,
+                SpineLod {
+                    levels: vec![SpineLodLevel::new(f32::INFINITY, every_third)],
+                    invisible: every_third,
+                    ..default()
+                },
+                GlobalTransform::default(),
+            ))
+            .id();
+
+        // the first update always runs, then two frames are skipped
+        app.update();
+        let first = track_time(&app, entity);
+        app.update();
+        app.update();
+        assert_eq!(track_time(&app, entity), first);
+        app.update();
+        let elapsed = app.world().resource::<Time>().elapsed_secs();
+        assert!((track_time(&app, entity) - elapsed).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,217 @@
+//! Crossfades shared between every Spine entity using the same skeleton.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use bevy::prelude::*;
+use rusty_spine::AnimationStateData;
+use serde::{Deserialize, Serialize};
+
+use crate::{Crossfades, SkeletonData, assets::RonAsset};
+
+/// Mix durations between animations, loaded from `.spinemix.ron` files.
+///
+/// Unlike [`Crossfades`], which builds new animation state data for every entity, the animation
+/// state data for a mix table is built once per [`SkeletonData`] and shared by every entity using
+/// both.
+///
+/// ```ron
+/// (
+///     default_mix: 0.1,
+///     mixes: [
+///         (from: "walk", to: "run", mix: 0.2),
+///         (from: "run", to: "walk", mix: 0.2),
+///         (from: "*", to: "idle", mix: 0.3),
+///     ],
+/// )
+/// ```
+///
+/// Names follow the same rules as [`Crossfades::add`], including `"*"` wildcards. To use a mix
+/// table, see [`SpineMixTableHandle`].
+#[derive(Asset, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct SpineMixTable {
+    /// The mix duration used for pairs of animations without an entry.
+    #[serde(default)]
+    pub default_mix: f32,
+    #[serde(default)]
+    pub mixes: Vec<SpineMix>,
+}
+
+/// A mix duration between two animations in a [`SpineMixTable`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct SpineMix {
+    pub from: String,
+    pub to: String,
+    pub mix: f32,
+}
+
+impl SpineMixTable {
+    pub fn crossfades(&self) -> Crossfades {
+        let mut crossfades = Crossfades::new();
+        crossfades.set_default_mix(self.default_mix);
+        for mix in self.mixes.iter() {
+            crossfades.add(&mix.from, &mix.to, mix.mix);
+        }
+        crossfades
+    }
+
+    /// The crossfades of an entity, with its [`Crossfades`] layered over its mix table.
+    pub(crate) fn layer(
+        mix_table: Option<&SpineMixTable>,
+        crossfades: Option<&Crossfades>,
+    ) -> Crossfades {
+        let mut layered = mix_table.map(SpineMixTable::crossfades).unwrap_or_default();
+        if let Some(crossfades) = crossfades {
+            layered.extend(crossfades);
+        }
+        layered
+    }
+}
+
+/// Use a [`SpineMixTable`] for the mixes of this Spine entity.
+///
+/// The skeleton waits for the mix table to load before it is spawned.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// let mix_table = asset_server.load("spineboy/spineboy.spinemix.ron");
+/// for x in 0..100 {
+///     commands.spawn((
+///         SkeletonDataHandle::default(),
+///         SpineMixTableHandle(mix_table.clone()),
+///         Transform::from_xyz(x as f32 * 20., 0., 0.),
+///     ));
+/// }
+/// # }
+/// ```
+///
+/// If the entity also has non-empty [`Crossfades`] when the skeleton is ready, it gets its own
+/// animation state data, with its crossfades overriding the mix table. Otherwise, the animation
+/// state data is shared and later changes to [`Crossfades`] are ignored.
+#[derive(Component, Default, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct SpineMixTableHandle(pub Handle<SpineMixTable>);
+
+impl From<Handle<SpineMixTable>> for SpineMixTableHandle {
+    fn from(handle: Handle<SpineMixTable>) -> Self {
+        Self(handle)
+    }
+}
+
+impl RonAsset for SpineMixTable {
+    const EXTENSIONS: &'static [&'static str] = &["spinemix.ron"];
+}
+
+type AnimationStateDataKey = (AssetId<SkeletonData>, AssetId<SpineMixTable>);
+
+/// Animation state data shared by every entity using the same skeleton and mix table.
+#[derive(Default, Resource)]
+pub(crate) struct SpineAnimationStateDataCache(
+    HashMap<AnimationStateDataKey, (Weak<rusty_spine::SkeletonData>, Arc<AnimationStateData>)>,
+);
+
+impl SpineAnimationStateDataCache {
+    pub(crate) fn get(
+        &mut self,
+        key: AnimationStateDataKey,
+        skeleton_data: &Arc<rusty_spine::SkeletonData>,
+        mix_table: &SpineMixTable,
+    ) -> Arc<AnimationStateData> {
+        // a reloaded skeleton replaces its skeleton data, so the cached data is stale
+        if let Some((cached_skeleton_data, animation_state_data)) = self.0.get(&key)
+            && cached_skeleton_data.ptr_eq(&Arc::downgrade(skeleton_data))
+        {
+            return animation_state_data.clone();
+        }
+        let mut animation_state_data = AnimationStateData::new(skeleton_data.clone());
+        mix_table
+            .crossfades()
+            .apply(&mut animation_state_data, skeleton_data);
+        let animation_state_data = Arc::new(animation_state_data);
+        self.0.insert(
+            key,
+            (Arc::downgrade(skeleton_data), animation_state_data.clone()),
+        );
+        animation_state_data
+    }
+}
+
+pub(crate) fn spine_mix_table_changed(
+    mut mix_table_events: MessageReader<AssetEvent<SpineMixTable>>,
+    mut skeleton_data_events: MessageReader<AssetEvent<SkeletonData>>,
+    mut cache: ResMut<SpineAnimationStateDataCache>,
+) {
+    for event in mix_table_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            cache.0.retain(|(_, mix_table_id), _| mix_table_id != id);
+        }
+    }
+    for event in skeleton_data_events.read() {
+        if let AssetEvent::Removed { id } = event {
+            cache
+                .0
+                .retain(|(skeleton_data_id, _), _| skeleton_data_id != id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::prelude::*;
+
+    use super::{SpineAnimationStateDataCache, SpineMix, SpineMixTable};
+    use crate::{SkeletonData, test_support::spineboy_data};
+
+    #[test]
+    fn cache_shares_animation_state_data() {
+        let skeleton_data = spineboy_data();
+        let mix_table = SpineMixTable {
+            default_mix: 0.1,
+            mixes: vec![SpineMix {
+                from: "*".to_owned(),
+                to: "idle".to_owned(),
+                mix: 0.3,
+            }],
+        };
+        let mut mix_tables = Assets::<SpineMixTable>::default();
+        let (mix_table_id, other_mix_table_id) = (
+            mix_tables.add(mix_table.clone()).id(),
+            mix_tables.add(mix_table.clone()).id(),
+        );
+        let skeleton_data_id = AssetId::<SkeletonData>::default();
+        let mut cache = SpineAnimationStateDataCache::default();
+        let key = (skeleton_data_id, mix_table_id);
+        let animation_state_data = cache.get(key, &skeleton_data, &mix_table);
+        assert!(Arc::ptr_eq(
+            &animation_state_data,
+            &cache.get(key, &skeleton_data, &mix_table)
+        ));
+        assert_eq!(animation_state_data.default_mix(), 0.1);
+
+        // each mix table and each reloaded skeleton gets its own data
+        assert!(!Arc::ptr_eq(
+            &animation_state_data,
+            &cache.get(
+                (skeleton_data_id, other_mix_table_id),
+                &skeleton_data,
+                &mix_table
+            )
+        ));
+        let reloaded = spineboy_data();
+        let reloaded_animation_state_data = cache.get(key, &reloaded, &mix_table);
+        assert!(!Arc::ptr_eq(
+            &animation_state_data,
+            &reloaded_animation_state_data
+        ));
+        assert!(Arc::ptr_eq(
+            &reloaded_animation_state_data,
+            &cache.get(key, &reloaded, &mix_table)
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! Root motion, moving the entity instead of the skeleton.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rusty_spine::{Animation, Physics, TrackEntry};
+
+use crate::{
+    SkeletonData, SkeletonDataHandle, SkeletonDataStatus, Spine, SpinePaused, SpinePoseSampler,
+    lod::SpineLodState,
+};
+
+/// Extracts the animated motion of a bone and moves the entity instead of the skeleton.
+///
+/// Every frame, right after the skeleton is updated in
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation), the motion of
+/// [`SpineRootMotion::bone`] on the enabled axes is removed from the skeleton (the bone is kept at
+/// its setup pose on those axes) and either applied to the entity's [`Transform`] or only exposed
+/// through [`SpineRootMotion::velocity`], depending on [`SpineRootMotion::mode`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineRootMotion::new("root"),
+/// ));
+/// # }
+///
+/// fn print_root_motion(root_motion_query: Query<&SpineRootMotion>) {
+///     for root_motion in root_motion_query.iter() {
+///         println!("moving at {}", root_motion.velocity());
+///     }
+/// }
+/// ```
+///
+/// Motion is measured per track entry, so animation loops do not move the entity back to the
+/// start, and mixing between two animations blends their motion rather than their poses. Track
+/// alphas and mixes between tracks are taken into account, but the bone must only be keyed by
+/// animations with translate and rotate timelines for the extracted axes to match exactly.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct SpineRootMotion {
+    /// The name of the bone to extract motion from, usually the root bone.
+    pub bone: String,
+    /// Extract motion along the X axis (default: `true`).
+    pub x: bool,
+    /// Extract motion along the Y axis (default: `true`).
+    pub y: bool,
+    /// Extract rotation (default: `false`).
+    pub rotation: bool,
+    pub mode: SpineRootMotionMode,
+    delta: Vec2,
+    delta_rotation: f32,
+    velocity: Vec2,
+    angular_velocity: f32,
+    #[reflect(ignore)]
+    track_times: HashMap<usize, f32>,
+    #[reflect(ignore)]
+    initialized: bool,
+}
+
+/// What to do with the motion extracted by [`SpineRootMotion`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum SpineRootMotionMode {
+    /// Move the entity's [`Transform`].
+    #[default]
+    Transform,
+    /// Leave the [`Transform`] alone, for example to move the entity with a physics engine using
+    /// [`SpineRootMotion::velocity`].
+    Velocity,
+}
+
+impl Default for SpineRootMotion {
+    fn default() -> Self {
+        Self::new("root")
+    }
+}
+
+impl SpineRootMotion {
+    /// Extract translation from a bone, applying it to the entity's [`Transform`].
+    pub fn new(bone: &str) -> Self {
+        Self {
+            bone: bone.to_owned(),
+            x: true,
+            y: true,
+            rotation: false,
+            mode: SpineRootMotionMode::Transform,
+            delta: Vec2::ZERO,
+            delta_rotation: 0.,
+            velocity: Vec2::ZERO,
+            angular_velocity: 0.,
+            track_times: HashMap::new(),
+            initialized: false,
+        }
+    }
+
+    /// The translation extracted this frame, in the entity's local space.
+    pub fn delta(&self) -> Vec2 {
+        self.delta
+    }
+
+    /// The rotation extracted this frame, in radians.
+    pub fn delta_rotation(&self) -> f32 {
+        self.delta_rotation
+    }
+
+    /// The extracted translation per second, in the same space as the entity's
+    /// [`Transform::translation`].
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// The extracted rotation per second, in radians.
+    pub fn angular_velocity(&self) -> f32 {
+        self.angular_velocity
+    }
+
+    /// Forget the track times measured so far, so that no motion is extracted on the next frame.
+    ///
+    /// Track times jumping is not motion. This is done automatically after a
+    /// [`SpineSeek`](crate::SpineSeek), but must be called after [`Spine::restore`].
+    pub fn reset(&mut self) {
+        self.track_times.clear();
+        self.initialized = false;
+    }
+}
+
+/// Samples the pose of a bone in animations, see [`SpinePoseSampler`].
+struct RootMotionSampler<'a> {
+    sampler: &'a mut SpinePoseSampler,
+    bone: &'a str,
+}
+
+impl RootMotionSampler<'_> {
+    /// The local x, y and rotation of the bone at a time in an animation.
+    fn sample(&mut self, animation: &Animation, time: f32) -> Vec3 {
+        self.sampler.pose_animation(animation, time, false);
+        self.sampler
+            .skeleton()
+            .find_bone(self.bone)
+            .map(|bone| Vec3::new(bone.x(), bone.y(), bone.rotation()))
+            .unwrap_or_default()
+    }
+
+    /// The motion of the bone in an animation between two track times.
+    fn animation_delta(
+        &mut self,
+        animation: &Animation,
+        looping: bool,
+        from: f32,
+        to: f32,
+    ) -> Vec3 {
+        // negative track times are used for delayed entries which have not started yet
+        let (from, to) = (from.max(0.), to.max(0.));
+        let duration = animation.duration();
+        if duration <= 0. || from == to {
+            return Vec3::ZERO;
+        }
+        if looping {
+            let loops = (to / duration).floor() - (from / duration).floor();
+            let mut delta =
+                self.sample(animation, to % duration) - self.sample(animation, from % duration);
+            if loops != 0. {
+                delta += (self.sample(animation, duration) - self.sample(animation, 0.)) * loops;
+            }
+            delta
+        } else {
+            self.sample(animation, to.min(duration)) - self.sample(animation, from.min(duration))
+        }
+    }
+
+    /// The weighted motion of a track entry and the entries it is mixing from, and their total
+    /// weight.
+    fn track_entry_delta(
+        &mut self,
+        track_entry: &TrackEntry,
+        weight: f32,
+        previous_track_times: &HashMap<usize, f32>,
+        track_times: &mut HashMap<usize, f32>,
+    ) -> (Vec3, f32) {
+        let key = track_entry.c_ptr() as usize;
+        let track_time = track_entry.track_time();
+        track_times.insert(key, track_time);
+
+        let mix = if track_entry.mixing_from().is_some() && track_entry.mix_duration() > 0. {
+            (track_entry.mix_time() / track_entry.mix_duration()).min(1.)
+        } else {
+            1.
+        };
+        let entry_weight = weight * track_entry.alpha() * mix;
+        let previous_track_time = previous_track_times.get(&key).copied().unwrap_or(0.);
+        let mut delta = self.animation_delta(
+            &track_entry.animation(),
+            track_entry.looping(),
+            previous_track_time,
+            track_time,
+        ) * entry_weight;
+        let mut total_weight = entry_weight;
+
+        if let Some(mixing_from) = track_entry.mixing_from() {
+            let (from_delta, from_weight) = self.track_entry_delta(
+                &mixing_from,
+                weight * (1. - mix),
+                previous_track_times,
+                track_times,
+            );
+            delta += from_delta;
+            total_weight += from_weight;
+        }
+        (delta, total_weight)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_root_motion(
+    mut spine_query: Query<(
+        &mut Spine,
+        &mut SpineRootMotion,
+        &mut Transform,
+        &SkeletonDataHandle,
+        &SpineLodState,
+        Has<SpinePaused>,
+    )>,
+    mut samplers: Local<HashMap<AssetId<SkeletonData>, SpinePoseSampler>>,
+    skeleton_data_assets: Res<Assets<SkeletonData>>,
+) {
+    for (mut spine, mut root_motion, mut transform, data_handle, lod_state, paused) in
+        spine_query.iter_mut()
+    {
+        if !lod_state.update {
+            // the animation did not move, see `SpineLod`
+            root_motion.delta = Vec2::ZERO;
+            root_motion.delta_rotation = 0.;
+            if paused {
+                root_motion.velocity = Vec2::ZERO;
+                root_motion.angular_velocity = 0.;
+            }
+            continue;
+        }
+        let Some(SkeletonData {
+            status: SkeletonDataStatus::Loaded(skeleton_data),
+            ..
+        }) = skeleton_data_assets.get(&data_handle.0)
+        else {
+            continue;
+        };
+        let root_motion = root_motion.as_mut();
+        if lod_state.applied_seek {
+            root_motion.reset();
+        }
+        let mut sampler = RootMotionSampler {
+            sampler: samplers
+                .entry(data_handle.0.id())
+                .or_insert_with(|| SpinePoseSampler::new(skeleton_data.clone())),
+            bone: &root_motion.bone,
+        };
+
+        // tracks are applied in order, so each track mixes over the motion of all previous tracks
+        let mut local_delta = Vec3::ZERO;
+        let mut track_times = HashMap::new();
+        for track_entry in spine.animation_state.tracks().flatten() {
+            let (delta, weight) = sampler.track_entry_delta(
+                &track_entry,
+                1.,
+                &root_motion.track_times,
+                &mut track_times,
+            );
+            local_delta = local_delta * (1. - weight.min(1.)) + delta;
+        }
+        root_motion.track_times = track_times;
+        if !root_motion.initialized {
+            // track times are unknown before the first frame or after a seek, so no motion can be
+            // measured yet
+            root_motion.initialized = true;
+            local_delta = Vec3::ZERO;
+        }
+        let local_delta = local_delta
+            * Vec3::new(
+                root_motion.x as u8 as f32,
+                root_motion.y as u8 as f32,
+                root_motion.rotation as u8 as f32,
+            );
+
+        // remove the motion from the skeleton, and convert it to skeleton space
+        let Spine(controller) = spine.as_mut();
+        let (scale_x, scale_y) = (controller.skeleton.scale_x(), controller.skeleton.scale_y());
+        let Some(mut bone) = controller.skeleton.find_bone_mut(&root_motion.bone) else {
+            continue;
+        };
+        let parent_matrix = bone
+            .parent()
+            .map(|parent| {
+                Mat2::from_cols(
+                    Vec2::new(parent.a(), parent.c()),
+                    Vec2::new(parent.b(), parent.d()),
+                )
+            })
+            .unwrap_or(Mat2::from_diagonal(Vec2::new(scale_x, scale_y)));
+        let (setup_x, setup_y, setup_rotation) =
+            (bone.data().x(), bone.data().y(), bone.data().rotation());
+        if root_motion.x {
+            bone.set_x(setup_x);
+        }
+        if root_motion.y {
+            bone.set_y(setup_y);
+        }
+        if root_motion.rotation {
+            bone.set_rotation(setup_rotation);
+        }
+        controller.skeleton.update_world_transform(Physics::Pose);
+
+        root_motion.delta = parent_matrix * local_delta.truncate();
+        root_motion.delta_rotation =
+            local_delta.z.to_radians() * parent_matrix.determinant().signum();
+        let translation_delta =
+            transform.rotation * (root_motion.delta.extend(0.) * transform.scale);
+        // skipped frames are applied at once, see `SpineLod`
+        let delta_seconds = lod_state.applied_delta;
+        if delta_seconds > 0. {
+            root_motion.velocity = translation_delta.truncate() / delta_seconds;
+            root_motion.angular_velocity = root_motion.delta_rotation / delta_seconds;
+        }
+        if root_motion.mode == SpineRootMotionMode::Transform {
+            transform.translation += translation_delta;
+            transform.rotate_local_z(root_motion.delta_rotation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::prelude::*;
+    use rusty_spine::Physics;
+
+    use super::RootMotionSampler;
+    use crate::{
+        SpinePoseSampler,
+        test_support::{spine, spineboy_data},
+    };
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.abs_diff_eq(b, 1e-3), "{a} != {b}");
+    }
+
+    #[test]
+    fn animation_delta() {
+        let skeleton_data = spineboy_data();
+        let mut pose_sampler = SpinePoseSampler::new(skeleton_data.clone());
+        let mut sampler = RootMotionSampler {
+            sampler: &mut pose_sampler,
+            bone: "hip",
+        };
+        let walk = skeleton_data
+            .animations()
+            .find(|animation| animation.name() == "walk")
+            .unwrap();
+        let duration = walk.duration();
+        let loop_delta = sampler.sample(&walk, duration) - sampler.sample(&walk, 0.);
+
+        let from = sampler.sample(&walk, duration * 0.25);
+        let to = sampler.sample(&walk, duration * 0.5);
+        assert_near(
+            sampler.animation_delta(&walk, true, duration * 0.25, duration * 0.5),
+            to - from,
+        );
+        // wrapping around the end of a loop
+        let from = sampler.sample(&walk, duration * 0.75);
+        let to = sampler.sample(&walk, duration * 0.25);
+        assert_near(
+            sampler.animation_delta(&walk, true, duration * 0.75, duration * 1.25),
+            to - from + loop_delta,
+        );
+        assert_near(
+            sampler.animation_delta(&walk, true, duration * 0.5, duration * 2.5),
+            loop_delta * 2.,
+        );
+        // entries which do not loop hold their last pose
+        let from = sampler.sample(&walk, duration * 0.75);
+        let to = sampler.sample(&walk, duration);
+        assert_near(
+            sampler.animation_delta(&walk, false, duration * 0.75, duration * 1.25),
+            to - from,
+        );
+        // delayed entries have not started yet
+        assert_near(sampler.animation_delta(&walk, true, -0.5, -0.1), Vec3::ZERO);
+    }
+
+    #[test]
+    fn track_entry_delta_mixing() {
+        let skeleton_data = spineboy_data();
+        let mut spine = spine(skeleton_data.clone());
+        let mut pose_sampler = SpinePoseSampler::new(skeleton_data);
+        let mut sampler = RootMotionSampler {
+            sampler: &mut pose_sampler,
+            bone: "hip",
+        };
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0.5, Physics::Update);
+        spine
+            .animation_state
+            .set_animation_by_name(0, "run", true)
+            .unwrap();
+        spine.update(0., Physics::Update);
+        let mut previous_track_times = HashMap::new();
+        let (walk_from, run_from) = {
+            let track_entry = spine.animation_state.get_current(0).unwrap();
+            sampler.track_entry_delta(&track_entry, 1., &HashMap::new(), &mut previous_track_times);
+            let mixing_from = track_entry.mixing_from().unwrap();
+            (mixing_from.track_time(), track_entry.track_time())
+        };
+
+        spine.update(0.1, Physics::Update);
+        let track_entry = spine.animation_state.get_current(0).unwrap();
+        let mixing_from = track_entry.mixing_from().unwrap();
+        let mix = track_entry.mix_time() / track_entry.mix_duration();
+        assert!(mix > 0. && mix < 1., "{mix}");
+        let mut track_times = HashMap::new();
+        let (delta, weight) =
+            sampler.track_entry_delta(&track_entry, 1., &previous_track_times, &mut track_times);
+
+        let run_delta = sampler.animation_delta(
+            &track_entry.animation(),
+            true,
+            run_from,
+            track_entry.track_time(),
+        );
+        let walk_delta = sampler.animation_delta(
+            &mixing_from.animation(),
+            true,
+            walk_from,
+            mixing_from.track_time(),
+        );
+        assert!((weight - 1.).abs() < 1e-4, "{weight}");
+        assert_near(delta, run_delta * mix + walk_delta * (1. - mix));
+        assert_eq!(track_times.len(), 2);
+    }
+}
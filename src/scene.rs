@@ -0,0 +1,349 @@
+//! Saving the live state of Spine entities in scenes.
+
+use bevy::prelude::*;
+
+use crate::{Spine, SpineBone, SpineLoader};
+
+/// The state of a live [`Spine`], to be written into a
+/// [`DynamicScene`](bevy::scene::DynamicScene) and reapplied when the scene is loaded.
+///
+/// Add this component to every Spine entity which should be saved, and capture it right before
+/// saving with [`SpineState::capture_all`]. Since [`Spine`] and [`Handle`]s cannot be serialized,
+/// leave them out of the scene and insert the [`SkeletonDataHandle`](crate::SkeletonDataHandle)
+/// again after loading:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineState;
+/// fn save(world: &mut World) -> String {
+///     SpineState::capture_all(world);
+///     let entities: Vec<Entity> = world
+///         .query_filtered::<Entity, With<SpineState>>()
+///         .iter(world)
+///         .collect();
+///     let scene = DynamicSceneBuilder::from_world(world)
+///         .deny_component::<Spine>()
+///         .deny_component::<SkeletonDataHandle>()
+///         .extract_entities(entities.into_iter())
+///         .build();
+///     let type_registry = world.resource::<AppTypeRegistry>().read();
+///     scene.serialize(&type_registry).unwrap()
+/// }
+/// ```
+///
+/// Only the Spine entities themselves should be saved, their children are spawned again once the
+/// skeleton is loaded. When a scene is loaded, every [`SpineLoader`] without a live [`Spine`] goes
+/// back to [`SpineLoader::Loading`], and this state is applied before [`SpineReadyEvent`] is sent
+/// (see [`SpineSystem::Ready`]).
+///
+/// [`SpineReadyEvent`]: crate::SpineReadyEvent
+/// [`SpineSystem::Ready`]: crate::SpineSystem::Ready
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineState {
+    /// Spawn child entities for the bones when loaded again (see [`SpineLoader::with_children`]).
+    pub with_children: bool,
+    pub skin: Option<String>,
+    pub tracks: Vec<SpineTrackState>,
+    /// Slots showing an attachment other than their setup pose attachment.
+    pub attachments: Vec<SpineAttachmentState>,
+    /// Bones with a local pose other than their setup pose.
+    pub bones: Vec<SpineBoneState>,
+}
+
+/// The animation playing on a track, see [`SpineState`] and
+/// [`SpineInspector`](crate::SpineInspector).
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineTrackState {
+    pub track: usize,
+    pub animation: String,
+    pub looping: bool,
+    /// The track time in seconds.
+    pub time: f32,
+    pub time_scale: f32,
+    pub alpha: f32,
+}
+
+/// The attachment of a slot, see [`SpineState`].
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineAttachmentState {
+    pub slot: String,
+    pub attachment: Option<String>,
+}
+
+/// The local pose of a bone, see [`SpineState`] and [`SpineInspector`](crate::SpineInspector).
+#[derive(Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct SpineBoneState {
+    pub bone: String,
+    pub x: f32,
+    pub y: f32,
+    /// The rotation in degrees.
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+}
+
+impl Default for SpineState {
+    fn default() -> Self {
+        Self {
+            with_children: true,
+            skin: None,
+            tracks: vec![],
+            attachments: vec![],
+            bones: vec![],
+        }
+    }
+}
+
+impl SpineState {
+    /// Capture the state of a live [`Spine`].
+    pub fn capture(spine: &Spine, with_children: bool) -> Self {
+        let skeleton = &spine.skeleton;
+        Self {
+            with_children,
+            skin: skeleton.skin().map(|skin| skin.name().to_owned()),
+            tracks: spine
+                .animation_state
+                .tracks()
+                .enumerate()
+                .filter_map(|(track, track_entry)| {
+                    let track_entry = track_entry?;
+                    Some(SpineTrackState {
+                        track,
+                        animation: track_entry.animation().name().to_owned(),
+                        looping: track_entry.looping(),
+                        time: track_entry.track_time(),
+                        time_scale: track_entry.timescale(),
+                        alpha: track_entry.alpha(),
+                    })
+                })
+                .collect(),
+            attachments: skeleton
+                .slots()
+                .filter_map(|slot| {
+                    let attachment = slot
+                        .attachment()
+                        .map(|attachment| attachment.name().to_owned());
+                    if attachment.as_deref() == slot.data().attachment_name() {
+                        return None;
+                    }
+                    Some(SpineAttachmentState {
+                        slot: slot.data().name().to_owned(),
+                        attachment,
+                    })
+                })
+                .collect(),
+            bones: skeleton
+                .bones()
+                .filter_map(|bone| {
+                    let data = bone.data();
+                    let bone_state = SpineBoneState {
+                        bone: data.name().to_owned(),
+                        x: bone.x(),
+                        y: bone.y(),
+                        rotation: bone.rotation(),
+                        scale_x: bone.scale_x(),
+                        scale_y: bone.scale_y(),
+                    };
+                    let setup = (
+                        data.x(),
+                        data.y(),
+                        data.rotation(),
+                        data.scale_x(),
+                        data.scale_y(),
+                    );
+                    (setup
+                        != (
+                            bone_state.x,
+                            bone_state.y,
+                            bone_state.rotation,
+                            bone_state.scale_x,
+                            bone_state.scale_y,
+                        ))
+                        .then_some(bone_state)
+                })
+                .collect(),
+        }
+    }
+
+    /// Capture the state of every Spine entity with a [`SpineState`] component, see
+    /// [`SpineState::capture`].
+    pub fn capture_all(world: &mut World) {
+        if let Err(err) = world.run_system_cached(spine_state_capture) {
+            warn!("cannot capture spine states: {err}");
+        }
+    }
+
+    /// Apply this state to a live [`Spine`]. Unknown skins, animations, slots and bones are
+    /// skipped with a warning.
+    pub fn apply(&self, spine: &mut Spine) {
+        let Spine(controller) = spine;
+        if let Some(skin) = &self.skin
+            && controller.skeleton.set_skin_by_name(skin).is_err()
+        {
+            warn!("cannot restore unknown spine skin: {skin}");
+        }
+        controller.skeleton.set_slots_to_setup_pose();
+        for attachment in self.attachments.iter() {
+            if !controller
+                .skeleton
+                .set_attachment(&attachment.slot, attachment.attachment.as_deref())
+            {
+                warn!(
+                    "cannot restore unknown spine attachment: {} on slot {}",
+                    attachment.attachment.as_deref().unwrap_or("none"),
+                    attachment.slot
+                );
+            }
+        }
+        for bone_state in self.bones.iter() {
+            let Some(mut bone) = controller.skeleton.find_bone_mut(&bone_state.bone) else {
+                warn!("cannot restore unknown spine bone: {}", bone_state.bone);
+                continue;
+            };
+            bone.set_x(bone_state.x);
+            bone.set_y(bone_state.y);
+            bone.set_rotation(bone_state.rotation);
+            bone.set_scale_x(bone_state.scale_x);
+            bone.set_scale_y(bone_state.scale_y);
+        }
+        for track in self.tracks.iter() {
+            match controller.animation_state.set_animation_by_name(
+                track.track,
+                &track.animation,
+                track.looping,
+            ) {
+                Ok(mut track_entry) => {
+                    track_entry.set_track_time(track.time);
+                    track_entry.set_timescale(track.time_scale);
+                    track_entry.set_alpha(track.alpha);
+                }
+                Err(_) => warn!(
+                    "cannot restore unknown spine animation: {}",
+                    track.animation
+                ),
+            }
+        }
+    }
+}
+
+/// Scenes store [`SpineLoader::Ready`], which means nothing without a live [`Spine`], so load
+/// these entities again.
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_state_reload(
+    mut loader_query: Query<
+        (&mut SpineLoader, Option<&SpineState>),
+        (Added<SpineLoader>, Without<Spine>),
+    >,
+) {
+    for (mut spine_loader, spine_state) in loader_query.iter_mut() {
+        if !matches!(spine_loader.as_ref(), SpineLoader::Loading { .. }) {
+            *spine_loader = SpineLoader::Loading {
+                with_children: spine_state.is_none_or(|spine_state| spine_state.with_children),
+            };
+        }
+    }
+}
+
+pub(crate) fn spine_state_apply(mut spine_query: Query<(&mut Spine, &SpineState), Added<Spine>>) {
+    for (mut spine, spine_state) in spine_query.iter_mut() {
+        spine_state.apply(&mut spine);
+    }
+}
+
+fn spine_state_capture(
+    mut spine_query: Query<(&Spine, &mut SpineState, Option<&Children>)>,
+    bone_query: Query<(), With<SpineBone>>,
+) {
+    for (spine, mut spine_state, children) in spine_query.iter_mut() {
+        let with_children = children
+            .is_some_and(|children| children.iter().any(|child| bone_query.contains(child)));
+        spine_state.set_if_neq(SpineState::capture(spine, with_children));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::{entity::EntityHashMap, system::RunSystemOnce},
+        prelude::*,
+        scene::serde::SceneDeserializer,
+    };
+    use rusty_spine::Physics;
+    use serde::de::DeserializeSeed;
+
+    use super::{SpineState, spine_state_apply, spine_state_reload};
+    use crate::{Spine, SpineLoader, test_support::spineboy};
+
+    #[test]
+    fn scene_round_trip() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut type_registry = world.resource::<AppTypeRegistry>().write();
+            type_registry.register::<SpineState>();
+            type_registry.register::<SpineLoader>();
+        }
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0.3, Physics::Update);
+        let mut track_entry = spine.animation_state.track_at_index_mut(0).unwrap();
+        track_entry.set_timescale(0.5);
+        track_entry.set_alpha(0.8);
+        spine.skeleton.set_attachment("head-bb", Some("head"));
+        spine
+            .skeleton
+            .find_bone_mut("gun")
+            .unwrap()
+            .set_rotation(45.);
+        let entity = world
+            .spawn((spine, SpineState::default(), SpineLoader::Ready))
+            .id();
+
+        // save
+        SpineState::capture_all(&mut world);
+        let saved_state = world.get::<SpineState>(entity).unwrap().clone();
+        assert_eq!(saved_state.tracks[0].time_scale, 0.5);
+        assert!(!saved_state.attachments.is_empty());
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_component::<Spine>()
+            .extract_entities([entity].into_iter())
+            .build();
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let ron = scene.serialize(&type_registry.read()).unwrap();
+
+        // load
+        let mut loaded_world = World::new();
+        loaded_world.insert_resource(type_registry.clone());
+        let scene = SceneDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut ron::de::Deserializer::from_str(&ron).unwrap())
+        .unwrap();
+        let mut entity_map = EntityHashMap::default();
+        scene
+            .write_to_world(&mut loaded_world, &mut entity_map)
+            .unwrap();
+        let entity = entity_map[&entity];
+        loaded_world.run_system_once(spine_state_reload).unwrap();
+        assert!(matches!(
+            loaded_world.get::<SpineLoader>(entity).unwrap(),
+            SpineLoader::Loading {
+                with_children: false
+            }
+        ));
+
+        // the skeleton is loaded again
+        loaded_world.entity_mut(entity).insert(spineboy());
+        loaded_world.run_system_once(spine_state_apply).unwrap();
+        let spine = loaded_world.get::<Spine>(entity).unwrap();
+        assert_eq!(SpineState::capture(spine, false), saved_state);
+    }
+}
@@ -0,0 +1,295 @@
+//! Queries on loaded [`SkeletonData`], without a live Spine entity.
+
+use std::{
+    ffi::{CStr, c_char},
+    sync::Arc,
+};
+
+use bevy::prelude::*;
+use rusty_spine::{
+    Animation, Physics, Skeleton,
+    c::{
+        SP_MIX_BLEND_SETUP, SP_MIX_DIRECTION_IN, SP_TIMELINE_EVENT, spAnimation_apply,
+        spEventTimeline,
+    },
+};
+
+use crate::SkeletonData;
+
+/// An animation in a [`SkeletonData`], see [`SkeletonData::animations`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpineAnimationInfo {
+    pub name: String,
+    /// The duration in seconds.
+    pub duration: f32,
+}
+
+/// An event keyed in an animation, see [`SkeletonData::animation_events`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpineEventKey {
+    pub name: String,
+    /// The time this event is keyed at in the animation.
+    pub time: f32,
+    pub int: i32,
+    pub float: f32,
+    pub string: String,
+    pub audio_path: String,
+    pub volume: f32,
+    pub balance: f32,
+}
+
+/// A slot in a [`SkeletonData`], see [`SkeletonData::slots`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpineSlotInfo {
+    pub name: String,
+    /// The bone this slot is attached to.
+    pub bone: String,
+    /// The attachment visible in the setup pose.
+    pub attachment: Option<String>,
+}
+
+impl SkeletonData {
+    /// Every animation of this skeleton, or an empty list if it is not loaded yet.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_spine::prelude::*;
+    /// fn print_animations(
+    ///     skeleton_query: Query<&SkeletonDataHandle>,
+    ///     skeleton_data_assets: Res<Assets<SkeletonData>>,
+    /// ) {
+    ///     for skeleton_data_handle in skeleton_query.iter() {
+    ///         let Some(skeleton_data) = skeleton_data_assets.get(&skeleton_data_handle.0) else {
+    ///             continue;
+    ///         };
+    ///         for animation in skeleton_data.animations() {
+    ///             println!("{} lasts {}s", animation.name, animation.duration);
+    ///         }
+    ///         if let Some(events) = skeleton_data.animation_events("run") {
+    ///             for event in events {
+    ///                 println!("{} fires at {}s", event.name, event.time);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn animations(&self) -> Vec<SpineAnimationInfo> {
+        let Some(skeleton_data) = self.skeleton_data() else {
+            return vec![];
+        };
+        skeleton_data
+            .animations()
+            .map(|animation| SpineAnimationInfo {
+                name: animation.name().to_owned(),
+                duration: animation.duration(),
+            })
+            .collect()
+    }
+
+    /// The duration of an animation in seconds, or `None` if there is no such animation or this
+    /// skeleton is not loaded yet.
+    pub fn animation_duration(&self, animation: &str) -> Option<f32> {
+        self.skeleton_data()?
+            .find_animation(animation)
+            .map(|animation| animation.duration())
+    }
+
+    /// Every event keyed in an animation, ordered by time, or `None` if there is no such animation
+    /// or this skeleton is not loaded yet.
+    pub fn animation_events(&self, animation: &str) -> Option<Vec<SpineEventKey>> {
+        let skeleton_data = self.skeleton_data()?;
+        let animation = skeleton_data.find_animation(animation)?;
+        let mut events = event_keys(&animation);
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Some(events)
+    }
+
+    /// The names of every skin of this skeleton, or an empty list if it is not loaded yet.
+    pub fn skins(&self) -> Vec<String> {
+        let Some(skeleton_data) = self.skeleton_data() else {
+            return vec![];
+        };
+        skeleton_data
+            .skins()
+            .map(|skin| skin.name().to_owned())
+            .collect()
+    }
+
+    /// Every slot of this skeleton in setup draw order, or an empty list if it is not loaded yet.
+    pub fn slots(&self) -> Vec<SpineSlotInfo> {
+        let Some(skeleton_data) = self.skeleton_data() else {
+            return vec![];
+        };
+        skeleton_data
+            .slots()
+            .map(|slot| SpineSlotInfo {
+                name: slot.name().to_owned(),
+                bone: slot.bone_data().name().to_owned(),
+                attachment: slot.attachment_name().map(str::to_owned),
+            })
+            .collect()
+    }
+
+    /// The names of every bone of this skeleton, parents first, or an empty list if it is not
+    /// loaded yet.
+    pub fn bones(&self) -> Vec<String> {
+        let Some(skeleton_data) = self.skeleton_data() else {
+            return vec![];
+        };
+        skeleton_data
+            .bones()
+            .map(|bone| bone.name().to_owned())
+            .collect()
+    }
+
+    /// Create a [`SpinePoseSampler`] for this skeleton, or `None` if it is not loaded yet.
+    pub fn pose_sampler(&self) -> Option<SpinePoseSampler> {
+        self.skeleton_data().map(SpinePoseSampler::new)
+    }
+
+    /// The transform of a bone in skeleton space at a time in an animation, see
+    /// [`SpinePoseSampler`].
+    pub fn sample_bone(&self, animation: &str, time: f32, bone: &str) -> Option<Transform> {
+        let mut sampler = self.pose_sampler()?;
+        sampler.pose(animation, time)?;
+        sampler.bone_transform(bone)
+    }
+}
+
+/// Poses a skeleton which is not rendered, to know where bones will be at a time in an animation.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// fn gun_tip_when_shooting(skeleton_data: &SkeletonData) -> Option<Vec3> {
+///     let mut sampler = skeleton_data.pose_sampler()?;
+///     sampler.pose("shoot", 0.1)?;
+///     Some(sampler.bone_transform("gun-tip")?.translation)
+/// }
+/// ```
+///
+/// Poses only apply a single animation over the setup pose. Transforms are in skeleton space, the
+/// local space of the Spine entity.
+pub struct SpinePoseSampler {
+    skeleton: Skeleton,
+    skeleton_data: Arc<rusty_spine::SkeletonData>,
+}
+
+impl SpinePoseSampler {
+    pub(crate) fn new(skeleton_data: Arc<rusty_spine::SkeletonData>) -> Self {
+        Self {
+            skeleton: Skeleton::new(skeleton_data.clone()),
+            skeleton_data,
+        }
+    }
+
+    /// Pose the skeleton at a time in an animation, or return `None` if there is no such
+    /// animation. Times past the duration of the animation loop.
+    pub fn pose(&mut self, animation: &str, time: f32) -> Option<()> {
+        let skeleton_data = self.skeleton_data.clone();
+        let animation = skeleton_data.find_animation(animation)?;
+        self.pose_animation(&animation, time, true);
+        Some(())
+    }
+
+    /// Pose the skeleton at a time in an animation of this skeleton. Times past the duration of
+    /// the animation loop if `looping`, otherwise the last pose is held.
+    pub(crate) fn pose_animation(&mut self, animation: &Animation, time: f32, looping: bool) {
+        self.skeleton.set_to_setup_pose();
+        // SAFETY: the animation belongs to this skeleton's data, and no events are collected
+        unsafe {
+            spAnimation_apply(
+                animation.c_ptr(),
+                self.skeleton.c_ptr(),
+                time,
+                time,
+                looping.into(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                1.,
+                SP_MIX_BLEND_SETUP,
+                SP_MIX_DIRECTION_IN,
+            );
+        }
+        self.skeleton.update_world_transform(Physics::None);
+    }
+
+    /// The transform of a bone in skeleton space, or `None` if there is no such bone.
+    pub fn bone_transform(&self, bone: &str) -> Option<Transform> {
+        let bone = self.skeleton.find_bone(bone)?;
+        Some(Transform {
+            translation: Vec3::new(bone.world_x(), bone.world_y(), 0.),
+            rotation: Quat::from_rotation_z(bone.world_rotation_x().to_radians()),
+            scale: Vec3::new(bone.world_scale_x(), bone.world_scale_y(), 1.),
+        })
+    }
+
+    /// The posed skeleton, for anything not covered by this sampler.
+    pub fn skeleton(&self) -> &Skeleton {
+        &self.skeleton
+    }
+}
+
+/// Every event key of an animation, read from its event timelines.
+fn event_keys(animation: &Animation) -> Vec<SpineEventKey> {
+    fn string(c_string: *const c_char) -> String {
+        if c_string.is_null() {
+            return String::new();
+        }
+        // SAFETY: spine strings are null terminated
+        unsafe { CStr::from_ptr(c_string) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    let mut events = vec![];
+    // SAFETY: the timelines and their events are owned by the animation, which outlives this call
+    unsafe {
+        let timelines = &*(*animation.c_ptr()).timelines;
+        for index in 0..timelines.size as usize {
+            let timeline = *timelines.items.add(index);
+            if (*timeline).type_0 != SP_TIMELINE_EVENT {
+                continue;
+            }
+            let event_timeline = timeline as *mut spEventTimeline;
+            for frame in 0..(*timeline).frameCount as usize {
+                let event = &**(*event_timeline).events.add(frame);
+                let event_data = &*event.data;
+                events.push(SpineEventKey {
+                    name: string(event_data.name),
+                    time: event.time,
+                    int: event.intValue,
+                    float: event.floatValue,
+                    string: string(event.stringValue),
+                    audio_path: string(event_data.audioPath),
+                    volume: event.volume,
+                    balance: event.balance,
+                });
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::event_keys;
+    use crate::test_support::spineboy;
+
+    #[test]
+    fn event_keys_from_timelines() {
+        let spine = spineboy();
+        let skeleton_data = spine.skeleton.data();
+        let walk = skeleton_data.find_animation("walk").unwrap();
+        let events = event_keys(&walk);
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.name.as_str(), event.time))
+                .collect::<Vec<_>>(),
+            [("footstep", 0.), ("footstep", 0.5)]
+        );
+        let idle = skeleton_data.find_animation("idle").unwrap();
+        assert!(event_keys(&idle).is_empty());
+    }
+}
@@ -0,0 +1,219 @@
+//! Composing several skins into one.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use bevy::prelude::*;
+use rusty_spine::{Skeleton, Skin};
+
+use crate::{SkeletonData, SkeletonDataHandle, Spine};
+
+/// Skins to combine into one and apply to a [`Spine`], for example a body skin, a hat skin and a
+/// weapon skin.
+///
+/// The skins are combined in order, so later skins replace the attachments of earlier skins in the
+/// same slots. Whenever this component changes, the combined skin is applied and slots are reset
+/// to their setup pose. It is first applied right after [`SpineSet::OnReady`], before the skeleton
+/// is updated for the first time.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineSkins;
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineSkins::new(["skin-base", "nose/short", "eyes/violet", "hair/brown"]),
+/// ));
+/// # }
+///
+/// fn equip_hat(mut skins_query: Query<&mut SpineSkins>) {
+///     for mut skins in skins_query.iter_mut() {
+///         skins.0.push("accessories/hat-red-yellow".to_owned());
+///     }
+/// }
+/// ```
+///
+/// Combined skins are cached per [`SkeletonData`], so entities wearing the same outfit share one
+/// skin. Unknown skin names are skipped with a warning. An empty list removes the skin.
+///
+/// [`SpineSet::OnReady`]: crate::SpineSet::OnReady
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq, Reflect)]
+#[require(SpineSkinsState)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineSkins(pub Vec<String>);
+
+impl SpineSkins {
+    pub fn new<I, S>(skins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(skins.into_iter().map(Into::into).collect())
+    }
+}
+
+/// The skin applied by [`SpineSkins`], kept alive for as long as the skeleton uses it.
+#[derive(Component, Default)]
+pub(crate) struct SpineSkinsState {
+    skin: Option<Arc<Skin>>,
+}
+
+type SkinKey = (AssetId<SkeletonData>, Vec<String>);
+
+/// Combined skins shared by every entity wearing the same skins of the same skeleton. Skins are
+/// owned by the [`SpineSkinsState`] of those entities, and dropped once nobody wears them.
+#[derive(Default, Resource)]
+pub(crate) struct SpineSkinCache(HashMap<SkinKey, (Weak<rusty_spine::SkeletonData>, Weak<Skin>)>);
+
+impl SpineSkinCache {
+    fn get(
+        &mut self,
+        entity: Entity,
+        skeleton_data_id: AssetId<SkeletonData>,
+        skeleton_data: &Arc<rusty_spine::SkeletonData>,
+        skins: &[String],
+    ) -> Arc<Skin> {
+        let key = (skeleton_data_id, skins.to_vec());
+        // a reloaded skeleton replaces its skeleton data, so the cached skin is stale
+        if let Some((cached_skeleton_data, skin)) = self.0.get(&key)
+            && cached_skeleton_data.ptr_eq(&Arc::downgrade(skeleton_data))
+            && let Some(skin) = skin.upgrade()
+        {
+            return skin;
+        }
+        let (skin, unknown) = combine_skins(skeleton_data, skins);
+        if !unknown.is_empty() {
+            let available: Vec<String> = skeleton_data
+                .skins()
+                .map(|skin| skin.name().to_owned())
+                .collect();
+            warn!(
+                "unknown spine skins {unknown:?} in SpineSkins of entity {entity}, expected one of \
+                {available:?}"
+            );
+        }
+        let skin = Arc::new(skin);
+        self.0.retain(|_, (_, skin)| skin.strong_count() > 0);
+        self.0
+            .insert(key, (Arc::downgrade(skeleton_data), Arc::downgrade(&skin)));
+        skin
+    }
+}
+
+/// Combine skins of a skeleton into a new skin, skipping and returning the unknown skin names.
+fn combine_skins<'a>(
+    skeleton_data: &rusty_spine::SkeletonData,
+    skins: &'a [String],
+) -> (Skin, Vec<&'a str>) {
+    let mut skin = Skin::new(&skins.join("+"));
+    let mut unknown = vec![];
+    for name in skins.iter() {
+        match skeleton_data.find_skin(name) {
+            // SAFETY: both skins belong to the same skeleton data
+            Some(part) => unsafe { skin.add_skin(&part) },
+            None => unknown.push(name.as_str()),
+        }
+    }
+    (skin, unknown)
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_skins(
+    mut spine_query: Query<
+        (
+            Entity,
+            &mut Spine,
+            &SpineSkins,
+            &mut SpineSkinsState,
+            &SkeletonDataHandle,
+        ),
+        Or<(Changed<SpineSkins>, Added<Spine>)>,
+    >,
+    mut skin_cache: ResMut<SpineSkinCache>,
+    skeleton_data_assets: Res<Assets<SkeletonData>>,
+    mut skeleton_data_events: MessageReader<AssetEvent<SkeletonData>>,
+) {
+    for event in skeleton_data_events.read() {
+        if let AssetEvent::Removed { id } = event {
+            skin_cache
+                .0
+                .retain(|(skeleton_data_id, _), _| skeleton_data_id != id);
+        }
+    }
+    for (entity, mut spine, skins, mut skins_state, data_handle) in spine_query.iter_mut() {
+        let Spine(controller) = spine.as_mut();
+        if skins.0.is_empty() {
+            clear_skin(&mut controller.skeleton);
+            skins_state.skin = None;
+        } else {
+            let Some(skeleton_data) = skeleton_data_assets
+                .get(&data_handle.0)
+                .and_then(SkeletonData::skeleton_data)
+            else {
+                continue;
+            };
+            let skin = skin_cache.get(entity, data_handle.0.id(), &skeleton_data, &skins.0);
+            // SAFETY: the skin was combined from this skeleton's data, and is kept alive by
+            // `SpineSkinsState` for as long as the skeleton uses it
+            unsafe { controller.skeleton.set_skin_unchecked(&skin) };
+            skins_state.skin = Some(skin);
+        }
+        controller.skeleton.set_slots_to_setup_pose();
+    }
+}
+
+/// Remove the skin of a skeleton, which only shows the attachments of its default skin after.
+pub(crate) fn clear_skin(skeleton: &mut Skeleton) {
+    // SAFETY: spine accepts a null skin, removing the current one
+    unsafe {
+        rusty_spine::c::spSkeleton_setSkin(skeleton.c_ptr(), std::ptr::null_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::prelude::*;
+
+    use super::{SpineSkinCache, combine_skins};
+    use crate::{SkeletonData, test_support::spineboy_data};
+
+    #[test]
+    fn combine_reports_unknown_skins() {
+        let skeleton_data = spineboy_data();
+        let skins = ["default".to_owned(), "missing".to_owned()];
+        let (skin, unknown) = combine_skins(&skeleton_data, &skins);
+        assert_eq!(unknown, ["missing"]);
+        assert_eq!(skin.name(), "default+missing");
+        assert!(!skin.attachments().is_empty());
+    }
+
+    #[test]
+    fn cache_shares_skins() {
+        let skeleton_data = spineboy_data();
+        let id = AssetId::<SkeletonData>::default();
+        let skins = ["default".to_owned()];
+        let mut skin_cache = SpineSkinCache::default();
+        let skin = skin_cache.get(Entity::PLACEHOLDER, id, &skeleton_data, &skins);
+        assert!(Arc::ptr_eq(
+            &skin,
+            &skin_cache.get(Entity::PLACEHOLDER, id, &skeleton_data, &skins)
+        ));
+
+        // a reloaded skeleton gets a new skin
+        let reloaded = spineboy_data();
+        assert!(!Arc::ptr_eq(
+            &skin,
+            &skin_cache.get(Entity::PLACEHOLDER, id, &reloaded, &skins)
+        ));
+
+        // skins nobody wears are dropped
+        let weak = Arc::downgrade(&skin);
+        drop(skin);
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,289 @@
+//! Hiding slots and changing the draw order without fighting animations.
+
+use std::{collections::HashSet, ptr::null_mut};
+
+use bevy::prelude::*;
+use rusty_spine::{
+    Skeleton,
+    c::{spAttachment, spSlot},
+};
+
+use crate::Spine;
+
+/// Hide slots, or draw them in front of or behind other slots, regardless of what animations key.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # use bevy_spine::SpineSlotOverrides;
+/// # fn doc(mut commands: Commands) {
+/// let mut slot_overrides = SpineSlotOverrides::default();
+/// // holding an item
+/// slot_overrides.hide("front-fist");
+/// slot_overrides.move_in_front("gun", "front-upper-arm");
+/// commands.spawn((SkeletonDataHandle::default(), slot_overrides));
+/// # }
+/// ```
+///
+/// Overrides are applied after [`SpineSystem::UpdateAnimation`] (and after [`SpineSync`] and
+/// [`SpineSlotSync`]), only for as long as [`SpineSystem::UpdateMeshes`] draws the skeleton. The
+/// attachments and draw order set by animations are restored right after, so animations keep
+/// playing as usual underneath and removing an override takes effect on the next frame.
+///
+/// Draw order moves are applied in order, each relative to the draw order left by the animations
+/// and earlier moves. Unknown slots are skipped with a warning.
+///
+/// [`SpineSystem::UpdateAnimation`]: crate::SpineSystem::UpdateAnimation
+/// [`SpineSystem::UpdateMeshes`]: crate::SpineSystem::UpdateMeshes
+/// [`SpineSync`]: crate::SpineSync
+/// [`SpineSlotSync`]: crate::SpineSlotSync
+#[derive(Component, Default, Debug, Clone, PartialEq, Reflect)]
+#[require(SpineSlotOverridesState)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct SpineSlotOverrides {
+    /// Slots to hide, by name.
+    pub hidden: HashSet<String>,
+    /// Slots to move in the draw order, applied in order.
+    pub draw_order: Vec<SpineDrawOrderMove>,
+}
+
+/// Move a slot in the draw order, see [`SpineSlotOverrides`].
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct SpineDrawOrderMove {
+    pub slot: String,
+    /// The slot to draw `slot` in front of or behind.
+    pub target: String,
+    pub placement: SpineDrawOrderPlacement,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash, Clone)]
+pub enum SpineDrawOrderPlacement {
+    /// Draw right after the target, on top of it.
+    #[default]
+    InFront,
+    /// Draw right before the target, underneath it.
+    Behind,
+}
+
+impl SpineSlotOverrides {
+    pub fn hide(&mut self, slot: &str) {
+        self.hidden.insert(slot.to_owned());
+    }
+
+    pub fn show(&mut self, slot: &str) {
+        self.hidden.remove(slot);
+    }
+
+    /// Draw `slot` on top of `target`, replacing any earlier move of `slot`.
+    pub fn move_in_front(&mut self, slot: &str, target: &str) {
+        self.move_slot(slot, target, SpineDrawOrderPlacement::InFront);
+    }
+
+    /// Draw `slot` underneath `target`, replacing any earlier move of `slot`.
+    pub fn move_behind(&mut self, slot: &str, target: &str) {
+        self.move_slot(slot, target, SpineDrawOrderPlacement::Behind);
+    }
+
+    /// Stop moving `slot` in the draw order.
+    pub fn reset_draw_order(&mut self, slot: &str) {
+        self.draw_order
+            .retain(|draw_order_move| draw_order_move.slot != slot);
+    }
+
+    fn move_slot(&mut self, slot: &str, target: &str, placement: SpineDrawOrderPlacement) {
+        self.reset_draw_order(slot);
+        self.draw_order.push(SpineDrawOrderMove {
+            slot: slot.to_owned(),
+            target: target.to_owned(),
+            placement,
+        });
+    }
+}
+
+/// What animations had set before [`SpineSlotOverrides`] were applied, restored once meshes are
+/// updated.
+#[derive(Component, Default)]
+pub(crate) struct SpineSlotOverridesState {
+    attachments: Vec<(*mut spSlot, *mut spAttachment)>,
+    draw_order: Vec<*mut spSlot>,
+}
+
+// SAFETY: the pointers are only used while the skeleton they belong to is borrowed through the
+// owning entity's `Spine`.
+unsafe impl Send for SpineSlotOverridesState {}
+unsafe impl Sync for SpineSlotOverridesState {}
+
+fn find_slot(skeleton: &Skeleton, name: &str, warn_unknown: bool) -> Option<*mut spSlot> {
+    let slot = skeleton.find_slot(name).map(|slot| slot.c_ptr());
+    if slot.is_none() && warn_unknown {
+        warn!("cannot override unknown spine slot: {name}");
+    }
+    slot
+}
+
+pub(crate) fn spine_slot_overrides_apply(
+    mut spine_query: Query<(
+        &mut Spine,
+        Ref<SpineSlotOverrides>,
+        &mut SpineSlotOverridesState,
+    )>,
+) {
+    for (mut spine, slot_overrides, mut state) in spine_query.iter_mut() {
+        let skeleton = &mut spine.skeleton;
+        let warn_unknown = slot_overrides.is_changed();
+        let state = state.as_mut();
+        state.attachments.clear();
+        state.draw_order.clear();
+
+        for name in slot_overrides.hidden.iter() {
+            let Some(slot) = find_slot(skeleton, name, warn_unknown) else {
+                continue;
+            };
+            // SAFETY: the slot belongs to this skeleton, the attachment is set back untouched in
+            // `spine_slot_overrides_restore`
+            unsafe {
+                state.attachments.push((slot, (*slot).attachment));
+                (*slot).attachment = null_mut();
+            }
+        }
+
+        if slot_overrides.draw_order.is_empty() {
+            continue;
+        }
+        // SAFETY: the draw order holds one pointer for every slot of this skeleton
+        let draw_order = unsafe {
+            let skeleton = skeleton.c_ptr();
+            std::slice::from_raw_parts_mut((*skeleton).drawOrder, (*skeleton).slotsCount as usize)
+        };
+        state.draw_order.extend_from_slice(draw_order);
+        let mut new_draw_order = state.draw_order.clone();
+        for draw_order_move in slot_overrides.draw_order.iter() {
+            let (Some(slot), Some(target)) = (
+                find_slot(skeleton, &draw_order_move.slot, warn_unknown),
+                find_slot(skeleton, &draw_order_move.target, warn_unknown),
+            ) else {
+                continue;
+            };
+            if slot == target {
+                continue;
+            }
+            let position = |slot| new_draw_order.iter().position(|&other| other == slot);
+            let (Some(slot_index), Some(mut target_index)) = (position(slot), position(target))
+            else {
+                continue;
+            };
+            new_draw_order.remove(slot_index);
+            if slot_index < target_index {
+                target_index -= 1;
+            }
+            let index = match draw_order_move.placement {
+                SpineDrawOrderPlacement::InFront => target_index + 1,
+                SpineDrawOrderPlacement::Behind => target_index,
+            };
+            new_draw_order.insert(index, slot);
+        }
+        draw_order.copy_from_slice(&new_draw_order);
+    }
+}
+
+pub(crate) fn spine_slot_overrides_restore(
+    mut spine_query: Query<(&mut Spine, &mut SpineSlotOverridesState)>,
+) {
+    for (mut spine, mut state) in spine_query.iter_mut() {
+        let skeleton = &mut spine.skeleton;
+        let state = state.as_mut();
+        // SAFETY: see `spine_slot_overrides_apply`
+        unsafe {
+            for (slot, attachment) in state.attachments.drain(..) {
+                (*slot).attachment = attachment;
+            }
+            if !state.draw_order.is_empty() {
+                let skeleton = skeleton.c_ptr();
+                std::slice::from_raw_parts_mut(
+                    (*skeleton).drawOrder,
+                    (*skeleton).slotsCount as usize,
+                )
+                .copy_from_slice(&state.draw_order);
+                state.draw_order.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    use rusty_spine::Physics;
+
+    use super::{SpineSlotOverrides, spine_slot_overrides_apply, spine_slot_overrides_restore};
+    use crate::{Spine, test_support::spineboy};
+
+    fn attachment(world: &World, entity: Entity, slot: &str) -> Option<String> {
+        let spine = world.get::<Spine>(entity).unwrap();
+        let slot = spine.skeleton.find_slot(slot).unwrap();
+        // SAFETY: the slot and its attachment belong to the skeleton borrowed above
+        unsafe {
+            let attachment = (*slot.c_ptr()).attachment;
+            (!attachment.is_null()).then(|| {
+                CStr::from_ptr((*attachment).name)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        }
+    }
+
+    fn draw_order(world: &World, entity: Entity) -> Vec<String> {
+        let spine = world.get::<Spine>(entity).unwrap();
+        spine
+            .skeleton
+            .draw_order()
+            .map(|slot| slot.data().name().to_owned())
+            .collect()
+    }
+
+    fn play(world: &mut World, entity: Entity, animation: &str) {
+        let mut spine = world.get_mut::<Spine>(entity).unwrap();
+        spine
+            .animation_state
+            .set_animation_by_name(0, animation, true)
+            .unwrap();
+        spine.update(0.1, Physics::Update);
+    }
+
+    #[test]
+    fn overrides_survive_animations() {
+        let mut world = World::new();
+        let mut slot_overrides = SpineSlotOverrides::default();
+        slot_overrides.hide("front-fist");
+        slot_overrides.move_in_front("gun", "front-upper-arm");
+        let entity = world.spawn((spineboy(), slot_overrides)).id();
+        play(&mut world, entity, "walk");
+        let animated_draw_order = draw_order(&world, entity);
+
+        for (animation, fist) in [("walk", "front-fist-closed"), ("idle", "front-fist-open")] {
+            play(&mut world, entity, animation);
+            world.run_system_once(spine_slot_overrides_apply).unwrap();
+            assert_eq!(attachment(&world, entity, "front-fist"), None);
+            let overridden_draw_order = draw_order(&world, entity);
+            let position = |name: &str| {
+                overridden_draw_order
+                    .iter()
+                    .position(|slot| slot == name)
+                    .unwrap()
+            };
+            assert_eq!(position("gun"), position("front-upper-arm") + 1);
+
+            // what animations set is back once meshes are updated
+            world.run_system_once(spine_slot_overrides_restore).unwrap();
+            assert_eq!(
+                attachment(&world, entity, "front-fist").as_deref(),
+                Some(fist)
+            );
+            assert_eq!(draw_order(&world, entity), animated_draw_order);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use rusty_spine::{SlotHandle, c::spColor};
+
+use crate::{Spine, SpineSynchronizer, SpineSystem};
+
+/// When added to a [`Spine`] entity, spawns a child entity for every slot of the skeleton once it
+/// is loaded, each containing a [`SpineSlot`] component.
+///
+/// Slot entities are not synchronized on their own, see [`SpineSlotSync`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::{SkeletonDataHandle, SpineSlots};
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineSlots,
+/// ));
+/// # }
+/// ```
+#[derive(Component, Default, Debug, Hash, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, Hash, PartialEq, Clone)]
+pub struct SpineSlots;
+
+/// A child entity of a [`Spine`] entity, representing one slot of the skeleton (see
+/// [`SpineSlots`]).
+///
+/// With [`SpineSlotSync`], the slot state is copied from the skeleton every frame after
+/// animations are applied, and any changes made to it are written back before the skeleton is
+/// rendered.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug, from_reflect = false)]
+pub struct SpineSlot {
+    pub spine_entity: Entity,
+    #[reflect(ignore)]
+    pub handle: SlotHandle,
+    pub name: String,
+    /// The name of the current attachment, or `None` if the slot is empty.
+    pub attachment: Option<String>,
+    pub color: Color,
+    /// The dark color used for two color tinting, or `None` if the slot does not use it.
+    pub dark_color: Option<Color>,
+}
+
+#[derive(Hash, Debug, PartialEq, Eq, Clone, Copy, SystemSet)]
+pub enum SpineSlotSynchronizerSystem<T: SpineSynchronizer> {
+    /// Set for [`spine_sync_slot_entities`]
+    SyncEntities,
+    /// Set for [`spine_sync_slots`]
+    SyncSlots,
+    _Data(PhantomData<T>),
+}
+
+/// Generic slot synchronization set. See [`SpineSlotSyncSet`] for example usage.
+#[derive(Hash, Debug, PartialEq, Eq, Clone, Copy, SystemSet)]
+pub enum SpineSlotSynchronizerSet<T: SpineSynchronizer> {
+    /// Occurs before all slot synchronization systems.
+    BeforeSync,
+    /// Occurs after synchronizing [`SpineSlot`] entities to the skeleton, but before writing them
+    /// back to the Spine skeleton. Useful for changing slot colors and attachments.
+    DuringSync,
+    /// Occurs after synchronizing [`SpineSlot`] entities back to the Spine skeleton.
+    AfterSync,
+    _Data(PhantomData<T>),
+}
+
+/// A plugin for synchronizing [`SpineSlot`] components with a rig.
+///
+/// This plugin is added automatically in [`SpinePlugin`](`crate::SpinePlugin`) for
+/// [`SpineSlotSync`] and does not need to be added manually. Like
+/// [`SpineSynchronizerPlugin`](crate::SpineSynchronizerPlugin), custom synchronization steps can
+/// be added to allow for multiple syncs in a single frame.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_spine::{prelude::*, SpineSlotSynchronizerPlugin, SpineSlotSyncSet, SpineSlots};
+///
+/// #[derive(Component, Debug, Hash, Clone, Copy, PartialEq, Eq)]
+/// pub struct MySpineSlotSync;
+/// pub type MySpineSlotSyncPlugin = SpineSlotSynchronizerPlugin<MySpineSlotSync, SpineSlotSyncSet>;
+///
+/// # fn doc() {
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             SpinePlugin,
+///             MySpineSlotSyncPlugin::default(),
+///         ))
+///         // ...
+///         .run();
+/// }
+/// # }
+///
+/// fn spawn(mut commands: Commands) {
+///     commands.spawn((
+///         SkeletonDataHandle::default(),
+///         SpineSlots,
+///         MySpineSlotSync,
+///     ));
+/// }
+/// ```
+pub struct SpineSlotSynchronizerPlugin<T: SpineSynchronizer, After: SystemSet + Copy> {
+    after: After,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SpineSynchronizer, S: SpineSynchronizer> Default
+    for SpineSlotSynchronizerPlugin<T, SpineSlotSynchronizerSet<S>>
+where
+    SpineSlotSynchronizerSet<S>: Copy,
+{
+    fn default() -> Self {
+        Self {
+            after: SpineSlotSynchronizerSet::<S>::AfterSync,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: SpineSynchronizer> SpineSlotSynchronizerPlugin<T, SpineSystem> {
+    pub(crate) fn first() -> Self {
+        Self {
+            after: SpineSystem::UpdateAnimation,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: SpineSynchronizer, A: SystemSet + Copy> Plugin for SpineSlotSynchronizerPlugin<T, A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spine_sync_slot_entities::<T>
+                    .in_set(SpineSlotSynchronizerSystem::<T>::SyncEntities)
+                    .after(self.after)
+                    .after(SpineSlotSynchronizerSet::<T>::BeforeSync)
+                    .before(SpineSlotSynchronizerSet::<T>::DuringSync),
+                spine_sync_slots::<T>
+                    .in_set(SpineSlotSynchronizerSystem::<T>::SyncSlots)
+                    .after(SpineSlotSynchronizerSystem::<T>::SyncEntities)
+                    .after(SpineSlotSynchronizerSet::<T>::DuringSync)
+                    .before(SpineSlotSynchronizerSet::<T>::AfterSync)
+                    .before(SpineSystem::UpdateMeshes),
+            ),
+        );
+    }
+}
+
+/// Synchronizes [`SpineSlot`] components to the Spine skeleton slots.
+pub fn spine_sync_slot_entities<S: SpineSynchronizer>(
+    mut slot_query: Query<&mut SpineSlot>,
+    spine_query: Query<&Spine, With<S>>,
+) {
+    for mut spine_slot in slot_query.iter_mut() {
+        if let Ok(spine) = spine_query.get(spine_slot.spine_entity)
+            && let Some(slot) = spine_slot.handle.get(&spine.skeleton)
+        {
+            let attachment = slot.attachment();
+            let attachment = attachment.as_ref().map(|attachment| attachment.name());
+            let color = to_bevy_color(slot.color());
+            let dark_color = slot.dark_color().map(to_bevy_color);
+            if spine_slot.attachment.as_deref() != attachment {
+                spine_slot.attachment = attachment.map(ToOwned::to_owned);
+            }
+            if spine_slot.color != color {
+                spine_slot.color = color;
+            }
+            if spine_slot.dark_color != dark_color {
+                spine_slot.dark_color = dark_color;
+            }
+        }
+    }
+}
+
+/// Synchronizes Spine skeleton slots to [`SpineSlot`] components.
+pub fn spine_sync_slots<S: SpineSynchronizer>(
+    slot_query: Query<&SpineSlot>,
+    mut spine_query: Query<&mut Spine, With<S>>,
+) {
+    for spine_slot in slot_query.iter() {
+        let Ok(mut spine) = spine_query.get_mut(spine_slot.spine_entity) else {
+            continue;
+        };
+        let attachment = {
+            let Some(mut slot) = spine_slot.handle.get_mut(&mut spine.skeleton) else {
+                continue;
+            };
+            *slot.color_mut() = to_spine_color(spine_slot.color);
+            if let Some(dark_color) = spine_slot.dark_color
+                && slot.dark_color().is_some()
+            {
+                let [r, g, b, a] = dark_color.to_linear().to_f32_array();
+                // SAFETY: the slot has a dark color, which it owns and which rusty_spine can only
+                // read
+                unsafe {
+                    *(*slot.c_ptr()).darkColor = spColor { r, g, b, a };
+                }
+            }
+            slot.attachment()
+                .map(|attachment| attachment.name().to_owned())
+        };
+        if attachment != spine_slot.attachment
+            && !spine
+                .skeleton
+                .set_attachment(&spine_slot.name, spine_slot.attachment.as_deref())
+        {
+            warn!(
+                "cannot set unknown spine attachment: {} on slot {}",
+                spine_slot.attachment.as_deref().unwrap_or("none"),
+                spine_slot.name
+            );
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn spine_spawn_slots(
+    mut commands: Commands,
+    spine_query: Query<(Entity, &Spine), (Added<Spine>, With<SpineSlots>)>,
+) {
+    for (spine_entity, spine) in spine_query.iter() {
+        for slot in spine.skeleton.slots() {
+            let name = slot.data().name().to_owned();
+            commands.spawn((
+                Name::new(format!("spine_slot ({name})")),
+                SpineSlot {
+                    spine_entity,
+                    handle: slot.handle(),
+                    attachment: slot
+                        .attachment()
+                        .map(|attachment| attachment.name().to_owned()),
+                    color: to_bevy_color(slot.color()),
+                    dark_color: slot.dark_color().map(to_bevy_color),
+                    name,
+                },
+                ChildOf(spine_entity),
+            ));
+        }
+    }
+}
+
+fn to_bevy_color(color: rusty_spine::Color) -> Color {
+    Color::linear_rgba(color.r, color.g, color.b, color.a)
+}
+
+fn to_spine_color(color: Color) -> rusty_spine::Color {
+    let [r, g, b, a] = color.to_linear().to_f32_array();
+    rusty_spine::Color::new_rgba(r, g, b, a)
+}
+
+/// A [`Component`] which synchronizes [`SpineSlot`] child entities with a [`Spine`] rig. Requires
+/// [`SpineSlots`], so the slot entities are spawned as well.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::{SkeletonDataHandle, SpineSlot, SpineSlotSync, SpineSlotSyncSet};
+/// # fn doc(mut commands: Commands) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineSlotSync,
+/// ));
+/// # }
+/// # fn app(app: &mut App) {
+/// app.add_systems(Update, flash.in_set(SpineSlotSyncSet::DuringSync));
+/// # }
+///
+/// fn flash(mut slot_query: Query<&mut SpineSlot>, time: Res<Time>) {
+///     for mut slot in slot_query.iter_mut() {
+///         let red = time.elapsed_secs().sin().abs();
+///         slot.color = Color::WHITE.mix(&Color::srgb(1., 0., 0.), red);
+///     }
+/// }
+/// ```
+///
+/// If multiple synchronization steps are needed, additional sync components can be created (see
+/// [`SpineSlotSynchronizerPlugin`]).
+#[derive(Component, Default, Debug, Hash, Clone, Copy, PartialEq, Eq, Reflect)]
+#[require(SpineSlots)]
+#[reflect(Component, Default, Debug, Hash, PartialEq, Clone)]
+pub struct SpineSlotSync;
+
+/// The default [`SpineSlotSynchronizerSystem`], see that struct for more docs.
+pub type SpineSlotSyncSystem = SpineSlotSynchronizerSystem<SpineSlotSync>;
+/// The default [`SpineSlotSynchronizerSet`], see that struct for more docs.
+///
+/// Add systems to [`SpineSlotSynchronizerSet::DuringSync`] to change slots of synchronized
+/// skeletons after animations have been applied.
+pub type SpineSlotSyncSet = SpineSlotSynchronizerSet<SpineSlotSync>;
+pub(crate) type SpineSlotSyncPlugin = SpineSlotSynchronizerPlugin<SpineSlotSync, SpineSystem>;
@@ -0,0 +1,506 @@
+//! Snapshots of the full state of a live skeleton, for rollback.
+
+use std::cell::Cell;
+
+use rusty_spine::{AnimationState, Physics, SpineError, TrackEntry, c::spPhysicsConstraint};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Spine, skins::clear_skin};
+
+thread_local! {
+    /// Set while [`Spine::restore`] replaces the tracks, see [`restoring`].
+    static RESTORING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether [`Spine::restore`] is replacing tracks on this thread, in which case the events fired
+/// by the replaced and restored entries are dropped instead of sent as [`SpineEvent`]s.
+///
+/// [`SpineEvent`]: crate::SpineEvent
+pub(crate) fn restoring() -> bool {
+    RESTORING.get()
+}
+
+/// Resets [`RESTORING`] when dropped, even if restoring a track fails.
+struct RestoringGuard;
+
+impl Drop for RestoringGuard {
+    fn drop(&mut self) {
+        RESTORING.set(false);
+    }
+}
+
+/// The name Spine gives to the animation of entries set with `set_empty_animation`.
+const EMPTY_ANIMATION: &str = "<empty>";
+
+/// The state of a [`Spine`] at one point in time, see [`Spine::snapshot`].
+///
+/// Snapshots hold the skin, every slot's attachment and color, the local pose of every bone, the
+/// state of physics constraints and every animation track, including the entries being mixed from
+/// and the entries queued after them. They can be serialized, for example to send them over the
+/// network.
+///
+/// Listeners, and the mixes of IK, transform and path constraints, are not part of a snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpineSnapshot {
+    pub skin: Option<String>,
+    pub time: f32,
+    pub time_scale: f32,
+    pub color: [f32; 4],
+    pub bones: Vec<SpineBoneSnapshot>,
+    pub slots: Vec<SpineSlotSnapshot>,
+    pub physics: Vec<SpinePhysicsSnapshot>,
+    /// The current entry of every track, by track index.
+    pub tracks: Vec<Option<SpineTrackSnapshot>>,
+}
+
+/// The local pose of a bone in a [`SpineSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpineBoneSnapshot {
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub shear_x: f32,
+    pub shear_y: f32,
+}
+
+/// A slot in a [`SpineSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpineSlotSnapshot {
+    pub attachment: Option<String>,
+    pub color: [f32; 4],
+}
+
+/// The simulation state of a physics constraint in a [`SpineSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpinePhysicsSnapshot {
+    pub reset: bool,
+    pub ux: f32,
+    pub uy: f32,
+    pub cx: f32,
+    pub cy: f32,
+    pub tx: f32,
+    pub ty: f32,
+    pub x_offset: f32,
+    pub x_velocity: f32,
+    pub y_offset: f32,
+    pub y_velocity: f32,
+    pub rotate_offset: f32,
+    pub rotate_velocity: f32,
+    pub scale_offset: f32,
+    pub scale_velocity: f32,
+    pub remaining: f32,
+    pub last_time: f32,
+}
+
+/// A track entry in a [`SpineSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpineTrackSnapshot {
+    pub animation: String,
+    pub looping: bool,
+    pub reverse: bool,
+    pub hold_previous: bool,
+    pub delay: f32,
+    pub track_time: f32,
+    pub track_last: f32,
+    pub next_track_last: f32,
+    pub animation_start: f32,
+    pub animation_end: f32,
+    pub animation_last: f32,
+    pub next_animation_last: f32,
+    pub time_scale: f32,
+    pub alpha: f32,
+    pub mix_time: f32,
+    pub mix_duration: f32,
+    /// The entry this entry is mixing from.
+    pub mixing_from: Option<Box<SpineTrackSnapshot>>,
+    /// The entry queued to play after this entry.
+    pub next: Option<Box<SpineTrackSnapshot>>,
+}
+
+#[derive(Debug, Error)]
+pub enum SpineSnapshotError {
+    #[error("Snapshot was taken from a different skeleton")]
+    SkeletonMismatch,
+    #[error("Spine error: {0}")]
+    Spine(#[from] SpineError),
+}
+
+impl Spine {
+    /// Capture the state of this skeleton and its animations, to [`Spine::restore`] it later.
+    ///
+    /// ```
+    /// # use bevy_spine::{Spine, rusty_spine::Physics};
+    /// # fn doc(spine: &mut Spine) {
+    /// let snapshot = spine.snapshot();
+    /// spine.update(1. / 60., Physics::Update);
+    /// // roll back, and replay the frame
+    /// spine.restore(&snapshot).unwrap();
+    /// spine.update(1. / 60., Physics::Update);
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> SpineSnapshot {
+        let skeleton = &self.skeleton;
+        SpineSnapshot {
+            skin: skeleton.skin().map(|skin| skin.name().to_owned()),
+            // SAFETY: the skeleton is borrowed for this call
+            time: unsafe { (*skeleton.c_ptr()).time },
+            time_scale: self.animation_state.timescale(),
+            color: color_array(skeleton.color()),
+            bones: skeleton
+                .bones()
+                .map(|bone| SpineBoneSnapshot {
+                    x: bone.x(),
+                    y: bone.y(),
+                    rotation: bone.rotation(),
+                    scale_x: bone.scale_x(),
+                    scale_y: bone.scale_y(),
+                    shear_x: bone.shear_x(),
+                    shear_y: bone.shear_y(),
+                })
+                .collect(),
+            slots: skeleton
+                .slots()
+                .map(|slot| SpineSlotSnapshot {
+                    attachment: slot
+                        .attachment()
+                        .map(|attachment| attachment.name().to_owned()),
+                    color: color_array(slot.color()),
+                })
+                .collect(),
+            physics: skeleton
+                .physics_constraints()
+                // SAFETY: the constraint is owned by the skeleton, which is borrowed for this call
+                .map(|constraint| physics_snapshot(unsafe { &*constraint.c_ptr() }))
+                .collect(),
+            tracks: self
+                .animation_state
+                .tracks()
+                .map(|track_entry| track_entry.map(|track_entry| track_snapshot(&track_entry)))
+                .collect(),
+        }
+    }
+
+    /// Restore the state captured by [`Spine::snapshot`]. The skeleton's world transforms are
+    /// updated, without stepping physics.
+    ///
+    /// No [`SpineEvent`]s are sent for the entries being replaced or restored. Restored entries are
+    /// new track entries, with new [`SpineTrackEntryId`]s. Call [`SpineRootMotion::reset`] after
+    /// restoring a skeleton with root motion. Returns an error if the snapshot was
+    /// taken from another skeleton, in which case this skeleton may be partially restored.
+    ///
+    /// [`SpineEvent`]: crate::SpineEvent
+    /// [`SpineTrackEntryId`]: crate::SpineTrackEntryId
+    /// [`SpineRootMotion::reset`]: crate::SpineRootMotion::reset
+    pub fn restore(&mut self, snapshot: &SpineSnapshot) -> Result<(), SpineSnapshotError> {
+        let Spine(controller) = self;
+        if snapshot.bones.len() != controller.skeleton.bones().count()
+            || snapshot.slots.len() != controller.skeleton.slots().count()
+            || snapshot.physics.len() != controller.skeleton.physics_constraints().count()
+        {
+            return Err(SpineSnapshotError::SkeletonMismatch);
+        }
+
+        match &snapshot.skin {
+            Some(skin) => controller.skeleton.set_skin_by_name(skin)?,
+            None => clear_skin(&mut controller.skeleton),
+        }
+        // SAFETY: the skeleton is mutably borrowed for this call
+        unsafe { (*controller.skeleton.c_ptr()).time = snapshot.time };
+        *controller.skeleton.color_mut() = color(snapshot.color);
+        for (mut bone, bone_snapshot) in controller.skeleton.bones_mut().zip(&snapshot.bones) {
+            bone.set_x(bone_snapshot.x);
+            bone.set_y(bone_snapshot.y);
+            bone.set_rotation(bone_snapshot.rotation);
+            bone.set_scale_x(bone_snapshot.scale_x);
+            bone.set_scale_y(bone_snapshot.scale_y);
+            bone.set_shear_x(bone_snapshot.shear_x);
+            bone.set_shear_y(bone_snapshot.shear_y);
+        }
+        let slot_names: Vec<String> = controller
+            .skeleton
+            .slots()
+            .map(|slot| slot.data().name().to_owned())
+            .collect();
+        for (slot_name, slot_snapshot) in slot_names.iter().zip(&snapshot.slots) {
+            if !controller
+                .skeleton
+                .set_attachment(slot_name, slot_snapshot.attachment.as_deref())
+            {
+                return Err(SpineSnapshotError::SkeletonMismatch);
+            }
+        }
+        for (mut slot, slot_snapshot) in controller.skeleton.slots_mut().zip(&snapshot.slots) {
+            *slot.color_mut() = color(slot_snapshot.color);
+        }
+        for (constraint, physics) in controller
+            .skeleton
+            .physics_constraints_mut()
+            .zip(&snapshot.physics)
+        {
+            // SAFETY: the constraint is owned by the skeleton, which is mutably borrowed
+            restore_physics(unsafe { &mut *constraint.c_ptr() }, physics);
+        }
+        controller.skeleton.update_world_transform(Physics::Pose);
+
+        RESTORING.set(true);
+        let _restoring = RestoringGuard;
+        let animation_state = &mut controller.animation_state;
+        animation_state.clear_tracks();
+        animation_state.set_timescale(snapshot.time_scale);
+        for (track, track_snapshot) in snapshot.tracks.iter().enumerate() {
+            if let Some(track_snapshot) = track_snapshot {
+                restore_track(animation_state, track, track_snapshot)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spine has no accessors for the simulation state of physics constraints, so it is read from the
+/// C struct.
+fn physics_snapshot(constraint: &spPhysicsConstraint) -> SpinePhysicsSnapshot {
+    SpinePhysicsSnapshot {
+        reset: constraint.reset != 0,
+        ux: constraint.ux,
+        uy: constraint.uy,
+        cx: constraint.cx,
+        cy: constraint.cy,
+        tx: constraint.tx,
+        ty: constraint.ty,
+        x_offset: constraint.xOffset,
+        x_velocity: constraint.xVelocity,
+        y_offset: constraint.yOffset,
+        y_velocity: constraint.yVelocity,
+        rotate_offset: constraint.rotateOffset,
+        rotate_velocity: constraint.rotateVelocity,
+        scale_offset: constraint.scaleOffset,
+        scale_velocity: constraint.scaleVelocity,
+        remaining: constraint.remaining,
+        last_time: constraint.lastTime,
+    }
+}
+
+fn restore_physics(constraint: &mut spPhysicsConstraint, physics: &SpinePhysicsSnapshot) {
+    constraint.reset = physics.reset.into();
+    constraint.ux = physics.ux;
+    constraint.uy = physics.uy;
+    constraint.cx = physics.cx;
+    constraint.cy = physics.cy;
+    constraint.tx = physics.tx;
+    constraint.ty = physics.ty;
+    constraint.xOffset = physics.x_offset;
+    constraint.xVelocity = physics.x_velocity;
+    constraint.yOffset = physics.y_offset;
+    constraint.yVelocity = physics.y_velocity;
+    constraint.rotateOffset = physics.rotate_offset;
+    constraint.rotateVelocity = physics.rotate_velocity;
+    constraint.scaleOffset = physics.scale_offset;
+    constraint.scaleVelocity = physics.scale_velocity;
+    constraint.remaining = physics.remaining;
+    constraint.lastTime = physics.last_time;
+}
+
+fn track_snapshot(track_entry: &TrackEntry) -> SpineTrackSnapshot {
+    // SAFETY: the track entry is borrowed for this call, these fields have no accessors
+    let c_track_entry = unsafe { &*track_entry.c_ptr() };
+    SpineTrackSnapshot {
+        animation: track_entry.animation().name().to_owned(),
+        looping: track_entry.looping(),
+        reverse: track_entry.reverse(),
+        hold_previous: track_entry.hold_previous(),
+        delay: track_entry.delay(),
+        track_time: track_entry.track_time(),
+        track_last: c_track_entry.trackLast,
+        next_track_last: c_track_entry.nextTrackLast,
+        animation_start: track_entry.animation_start(),
+        animation_end: track_entry.animation_end(),
+        animation_last: track_entry.animation_last(),
+        next_animation_last: c_track_entry.nextAnimationLast,
+        time_scale: track_entry.timescale(),
+        alpha: track_entry.alpha(),
+        mix_time: track_entry.mix_time(),
+        mix_duration: track_entry.mix_duration(),
+        mixing_from: track_entry
+            .mixing_from()
+            .map(|mixing_from| Box::new(track_snapshot(&mixing_from))),
+        next: track_entry
+            .next()
+            .map(|next| Box::new(track_snapshot(&next))),
+    }
+}
+
+/// Set the entries of a track, oldest first so that each entry mixes from the previous one.
+fn restore_track(
+    animation_state: &mut AnimationState,
+    track: usize,
+    track_snapshot: &SpineTrackSnapshot,
+) -> Result<(), SpineError> {
+    if let Some(mixing_from) = &track_snapshot.mixing_from {
+        // queued entries of an entry being mixed from were discarded when it was interrupted
+        restore_track(animation_state, track, mixing_from)?;
+    }
+    let mut track_entry = if track_snapshot.animation == EMPTY_ANIMATION {
+        animation_state.set_empty_animation(track, track_snapshot.mix_duration)
+    } else {
+        animation_state.set_animation_by_name(
+            track,
+            &track_snapshot.animation,
+            track_snapshot.looping,
+        )?
+    };
+    restore_track_entry(&mut track_entry, track_snapshot);
+
+    let mut next = track_snapshot.next.as_deref();
+    while let Some(next_snapshot) = next {
+        let mut next_entry = if next_snapshot.animation == EMPTY_ANIMATION {
+            animation_state.add_empty_animation(
+                track,
+                next_snapshot.mix_duration,
+                next_snapshot.delay,
+            )
+        } else {
+            animation_state.add_animation_by_name(
+                track,
+                &next_snapshot.animation,
+                next_snapshot.looping,
+                next_snapshot.delay,
+            )?
+        };
+        restore_track_entry(&mut next_entry, next_snapshot);
+        next = next_snapshot.next.as_deref();
+    }
+    Ok(())
+}
+
+fn restore_track_entry(track_entry: &mut TrackEntry, track_snapshot: &SpineTrackSnapshot) {
+    track_entry.set_looping(track_snapshot.looping);
+    track_entry.set_reverse(track_snapshot.reverse);
+    track_entry.set_hold_previous(track_snapshot.hold_previous);
+    track_entry.set_delay(track_snapshot.delay);
+    track_entry.set_track_time(track_snapshot.track_time);
+    track_entry.set_animation_start(track_snapshot.animation_start);
+    track_entry.set_animation_end(track_snapshot.animation_end);
+    track_entry.set_animation_last(track_snapshot.animation_last);
+    track_entry.set_timescale(track_snapshot.time_scale);
+    // SAFETY: the track entry is mutably borrowed for this call, these fields have no accessors
+    let c_track_entry = unsafe { &mut *track_entry.c_ptr() };
+    c_track_entry.trackLast = track_snapshot.track_last;
+    // entries which were never applied are replaced instead of mixed from, so this must be set
+    // before the next entry is set
+    c_track_entry.nextTrackLast = track_snapshot.next_track_last;
+    c_track_entry.nextAnimationLast = track_snapshot.next_animation_last;
+    track_entry.set_alpha(track_snapshot.alpha);
+    track_entry.set_mix_time(track_snapshot.mix_time);
+    track_entry.set_mix_duration(track_snapshot.mix_duration);
+}
+
+fn color_array(color: rusty_spine::Color) -> [f32; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+fn color(color: [f32; 4]) -> rusty_spine::Color {
+    rusty_spine::Color::new_rgba(color[0], color[1], color[2], color[3])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        mem::{Discriminant, discriminant},
+        sync::{Arc, Mutex},
+    };
+
+    use bevy::prelude::*;
+    use rusty_spine::Physics;
+
+    use super::SpineSnapshot;
+    use crate::{SpineEvent, SpineNames, set_spine_event_listener, test_support::spineboy};
+
+    #[test]
+    fn restore_replays_frames() {
+        let mut spine = spineboy();
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0.5, Physics::Update);
+        // take a snapshot in the middle of mixing from walk to run
+        spine
+            .animation_state
+            .set_animation_by_name(0, "run", true)
+            .unwrap();
+        spine.update(0.1, Physics::Update);
+        let snapshot = spine.snapshot();
+
+        spine.update(1.3, Physics::Update);
+        spine.restore(&snapshot).unwrap();
+        assert_eq!(spine.snapshot(), snapshot);
+
+        spine.update(0.05, Physics::Update);
+        let replayed = spine.snapshot();
+        spine.restore(&snapshot).unwrap();
+        spine.update(0.05, Physics::Update);
+        assert_eq!(spine.snapshot(), replayed);
+
+        let ron = ron::to_string(&snapshot).unwrap();
+        assert_eq!(ron::from_str::<SpineSnapshot>(&ron).unwrap(), snapshot);
+    }
+
+    /// The kind, track and track time of queued events, since restored entries get new ids.
+    fn drain(events: &Mutex<VecDeque<SpineEvent>>) -> Vec<(Discriminant<SpineEvent>, usize, f32)> {
+        events
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|event| {
+                let track = event.track();
+                (discriminant(&event), track.track_index, track.track_time)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn restore_sends_no_events() {
+        let mut spine = spineboy();
+        let events = Arc::new(Mutex::new(VecDeque::new()));
+        let names = Arc::new(SpineNames::new(&spine.skeleton.data()));
+        set_spine_event_listener(
+            &mut spine.animation_state,
+            Entity::PLACEHOLDER,
+            events.clone(),
+            names,
+        );
+        spine
+            .animation_state
+            .set_animation_by_name(0, "walk", true)
+            .unwrap();
+        spine.update(0.5, Physics::Update);
+        spine
+            .animation_state
+            .set_animation_by_name(0, "run", true)
+            .unwrap();
+        spine
+            .animation_state
+            .add_animation_by_name(0, "jump", false, 0.)
+            .unwrap();
+        spine.update(0.1, Physics::Update);
+        let snapshot = spine.snapshot();
+        events.lock().unwrap().clear();
+        spine.update(0.5, Physics::Update);
+        spine.update(0.5, Physics::Update);
+        let expected = drain(&events);
+        assert!(!expected.is_empty());
+
+        spine.restore(&snapshot).unwrap();
+        let restored_events = drain(&events);
+        assert!(restored_events.is_empty(), "{restored_events:?}");
+
+        // events of the restored entries are sent as usual afterwards
+        spine.update(0.5, Physics::Update);
+        spine.update(0.5, Physics::Update);
+        assert_eq!(drain(&events), expected);
+    }
+}
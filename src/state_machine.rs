@@ -0,0 +1,463 @@
+//! A data-driven animation state machine for Spine entities.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Spine, SpineEvent, assets::RonAsset};
+
+/// An animation state machine, loaded from `.spinesm.ron` files.
+///
+/// Each state maps tracks to animations. Transitions between states are guarded by parameters
+/// (see [`StateMachineParameter`]) and can wait for the current state's animations to complete.
+///
+/// ```ron
+/// (
+///     initial: "idle",
+///     parameters: {
+///         "speed": Float(0.0),
+///         "jump": Trigger(false),
+///     },
+///     states: {
+///         "idle": (tracks: [(track: 0, animation: "idle", looping: true)]),
+///         "run": (tracks: [(track: 0, animation: "run", looping: true)]),
+///         "jump": (tracks: [(track: 0, animation: "jump")]),
+///     },
+///     transitions: [
+///         (from: "idle", to: "run", mix: 0.2, conditions: [Greater("speed", 0.1)]),
+///         (from: "run", to: "idle", mix: 0.2, conditions: [Less("speed", 0.1)]),
+///         (from: "*", to: "jump", mix: 0.1, conditions: [If("jump")]),
+///         (from: "jump", to: "idle", mix: 0.2, exit_on_complete: true),
+///     ],
+/// )
+/// ```
+///
+/// To run a state machine on a Spine entity, see [`SpineStateMachine`]. Unknown states and
+/// parameters are reported once with a warning, when the state machine is first used.
+#[derive(Asset, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct AnimationStateMachine {
+    /// The state entered once the skeleton is ready.
+    pub initial: String,
+    /// Parameters and their default values.
+    #[serde(default)]
+    pub parameters: HashMap<String, StateMachineParameter>,
+    pub states: HashMap<String, AnimationStateMachineState>,
+    /// Transitions, checked in order. The first transition that passes is taken.
+    #[serde(default)]
+    pub transitions: Vec<StateTransition>,
+}
+
+/// A state in an [`AnimationStateMachine`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct AnimationStateMachineState {
+    /// Animations to play on each track while in this state.
+    ///
+    /// Tracks played by the previous state but not by this one are mixed out.
+    pub tracks: Vec<StateTrack>,
+}
+
+/// An animation played on a track by an [`AnimationStateMachineState`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct StateTrack {
+    pub track: usize,
+    pub animation: String,
+    #[serde(default)]
+    pub looping: bool,
+}
+
+/// A transition between two states in an [`AnimationStateMachine`].
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct StateTransition {
+    /// The state to transition from, or `"*"` for any state.
+    pub from: String,
+    pub to: String,
+    /// The mix duration applied to the animations of the new state.
+    #[serde(default)]
+    pub mix: f32,
+    /// All of these conditions must pass for this transition to be taken.
+    #[serde(default)]
+    pub conditions: Vec<TransitionCondition>,
+    /// If true, this transition is only taken after a [`SpineEvent::Complete`] was received for
+    /// one of the current state's animations.
+    #[serde(default)]
+    pub exit_on_complete: bool,
+}
+
+/// A condition on a [`StateMachineParameter`], see [`StateTransition::conditions`].
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum TransitionCondition {
+    /// Passes if a bool parameter is `true` or a trigger is set. Triggers are reset once a
+    /// transition using them is taken.
+    If(String),
+    /// Passes if a bool parameter is `false` or a trigger is not set.
+    IfNot(String),
+    /// Passes if a float parameter is greater than the value.
+    Greater(String, f32),
+    /// Passes if a float parameter is less than the value.
+    Less(String, f32),
+}
+
+/// A typed parameter of an [`AnimationStateMachine`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum StateMachineParameter {
+    Bool(bool),
+    Float(f32),
+    /// Like [`StateMachineParameter::Bool`], but reset after a transition uses it.
+    Trigger(bool),
+}
+
+impl StateMachineParameter {
+    fn is_set(&self) -> bool {
+        match *self {
+            StateMachineParameter::Bool(value) | StateMachineParameter::Trigger(value) => value,
+            StateMachineParameter::Float(value) => value != 0.,
+        }
+    }
+
+    fn float(&self) -> f32 {
+        match *self {
+            StateMachineParameter::Float(value) => value,
+            StateMachineParameter::Bool(value) | StateMachineParameter::Trigger(value) => {
+                value as u8 as f32
+            }
+        }
+    }
+}
+
+impl TransitionCondition {
+    fn passes(&self, parameters: &HashMap<String, StateMachineParameter>) -> bool {
+        match self {
+            TransitionCondition::If(name) => parameters.get(name).is_some_and(|p| p.is_set()),
+            TransitionCondition::IfNot(name) => !parameters.get(name).is_some_and(|p| p.is_set()),
+            TransitionCondition::Greater(name, value) => {
+                parameters.get(name).is_some_and(|p| p.float() > *value)
+            }
+            TransitionCondition::Less(name, value) => {
+                parameters.get(name).is_some_and(|p| p.float() < *value)
+            }
+        }
+    }
+
+    fn parameter(&self) -> &str {
+        match self {
+            TransitionCondition::If(name)
+            | TransitionCondition::IfNot(name)
+            | TransitionCondition::Greater(name, _)
+            | TransitionCondition::Less(name, _) => name,
+        }
+    }
+}
+
+impl AnimationStateMachine {
+    /// States, transition targets and condition parameters which do not exist.
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        if !self.states.contains_key(&self.initial) {
+            problems.push(format!("unknown initial state {:?}", self.initial));
+        }
+        for transition in self.transitions.iter() {
+            if transition.from != "*" && !self.states.contains_key(&transition.from) {
+                problems.push(format!("unknown transition state {:?}", transition.from));
+            }
+            if !self.states.contains_key(&transition.to) {
+                problems.push(format!("unknown transition state {:?}", transition.to));
+            }
+            for condition in transition.conditions.iter() {
+                if !self.parameters.contains_key(condition.parameter()) {
+                    problems.push(format!(
+                        "unknown parameter {:?} in transition from {:?} to {:?}",
+                        condition.parameter(),
+                        transition.from,
+                        transition.to
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+impl RonAsset for AnimationStateMachine {
+    const EXTENSIONS: &'static [&'static str] = &["spinesm.ron"];
+}
+
+/// Runs an [`AnimationStateMachine`] against the [`Spine`] on this entity.
+///
+/// Gameplay code only sets parameters, the state machine takes care of playing animations.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_spine::prelude::*;
+/// # fn doc(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// commands.spawn((
+///     SkeletonDataHandle::default(),
+///     SpineStateMachine::new(asset_server.load("spineboy/spineboy.spinesm.ron")),
+/// ));
+/// # }
+///
+/// fn move_player(mut state_machine_query: Query<&mut SpineStateMachine>) {
+///     for mut state_machine in state_machine_query.iter_mut() {
+///         state_machine.set_float("speed", 1.);
+///     }
+/// }
+/// ```
+///
+/// Transitions are evaluated in
+/// [`SpineSystem::UpdateAnimation`](crate::SpineSystem::UpdateAnimation), before the skeleton is
+/// updated. At most one transition is taken per frame.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct SpineStateMachine {
+    pub handle: Handle<AnimationStateMachine>,
+    parameters: HashMap<String, StateMachineParameter>,
+    state: Option<String>,
+    completed: bool,
+}
+
+impl SpineStateMachine {
+    pub fn new(handle: Handle<AnimationStateMachine>) -> Self {
+        Self {
+            handle,
+            parameters: HashMap::new(),
+            state: None,
+            completed: false,
+        }
+    }
+
+    /// The current state, or `None` if the state machine has not started yet.
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<StateMachineParameter> {
+        self.parameters.get(name).copied()
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.parameters
+            .insert(name.to_owned(), StateMachineParameter::Bool(value));
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        self.parameters
+            .insert(name.to_owned(), StateMachineParameter::Float(value));
+    }
+
+    /// Set a trigger, which stays set until a transition using it is taken.
+    pub fn set_trigger(&mut self, name: &str) {
+        self.parameters
+            .insert(name.to_owned(), StateMachineParameter::Trigger(true));
+    }
+
+    pub fn reset_trigger(&mut self, name: &str) {
+        self.parameters
+            .insert(name.to_owned(), StateMachineParameter::Trigger(false));
+    }
+
+    fn enter(
+        &mut self,
+        spine: &mut Spine,
+        state_machine: &AnimationStateMachine,
+        to: &str,
+        mix: f32,
+    ) {
+        // unknown states are reported once per asset, see `AnimationStateMachine::problems`
+        let Some(next) = state_machine.states.get(to) else {
+            return;
+        };
+        if let Some(previous) = self
+            .state
+            .as_ref()
+            .and_then(|state| state_machine.states.get(state))
+        {
+            for track in previous.tracks.iter() {
+                if !next.tracks.iter().any(|next| next.track == track.track) {
+                    spine.animation_state.set_empty_animation(track.track, mix);
+                }
+            }
+        }
+        for track in next.tracks.iter() {
+            match spine.animation_state.set_animation_by_name(
+                track.track,
+                &track.animation,
+                track.looping,
+            ) {
+                Ok(mut track_entry) => {
+                    if self.state.is_some() {
+                        track_entry.set_mix_duration(mix);
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "spine state machine failed to play {:?} in state {to:?}: {err}",
+                        track.animation
+                    );
+                }
+            }
+        }
+        self.state = Some(to.to_owned());
+        self.completed = false;
+    }
+}
+
+pub(crate) fn spine_state_machine(
+    mut state_machine_query: Query<(Entity, &mut Spine, &mut SpineStateMachine)>,
+    mut spine_events: MessageReader<SpineEvent>,
+    mut state_machine_events: MessageReader<AssetEvent<AnimationStateMachine>>,
+    state_machines: Res<Assets<AnimationStateMachine>>,
+    mut validated: Local<HashSet<AssetId<AnimationStateMachine>>>,
+) {
+    for event in state_machine_events.read() {
+        match event {
+            AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+                validated.remove(id);
+            }
+            _ => {}
+        }
+    }
+
+    for event in spine_events.read() {
+        if let SpineEvent::Complete {
+            entity,
+            animation,
+            track,
+        } = event
+            && let Ok((_, _, mut spine_state_machine)) = state_machine_query.get_mut(*entity)
+            && let Some(state_machine) = state_machines.get(&spine_state_machine.handle)
+            && let Some(state) = spine_state_machine
+                .state
+                .as_ref()
+                .and_then(|state| state_machine.states.get(state))
+            && state.tracks.iter().any(|state_track| {
+                state_track.track == track.track_index && animation == &state_track.animation
+            })
+        {
+            spine_state_machine.completed = true;
+        }
+    }
+
+    for (_, mut spine, mut spine_state_machine) in state_machine_query.iter_mut() {
+        let Some(state_machine) = state_machines.get(&spine_state_machine.handle) else {
+            continue;
+        };
+        if validated.insert(spine_state_machine.handle.id()) {
+            for problem in state_machine.problems() {
+                warn!(
+                    "spine state machine {:?}: {problem}",
+                    spine_state_machine.handle.path()
+                );
+            }
+        }
+        let Some(state) = spine_state_machine.state.clone() else {
+            for (name, parameter) in state_machine.parameters.iter() {
+                spine_state_machine
+                    .parameters
+                    .entry(name.clone())
+                    .or_insert(*parameter);
+            }
+            spine_state_machine.enter(&mut spine, state_machine, &state_machine.initial, 0.);
+            continue;
+        };
+        let transition = state_machine.transitions.iter().find(|transition| {
+            (transition.from == state || (transition.from == "*" && transition.to != state))
+                && (!transition.exit_on_complete || spine_state_machine.completed)
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.passes(&spine_state_machine.parameters))
+        });
+        if let Some(transition) = transition {
+            for condition in transition.conditions.iter() {
+                if let Some(parameter @ StateMachineParameter::Trigger(_)) = spine_state_machine
+                    .parameters
+                    .get_mut(condition.parameter())
+                {
+                    *parameter = StateMachineParameter::Trigger(false);
+                }
+            }
+            spine_state_machine.enter(&mut spine, state_machine, &transition.to, transition.mix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{AnimationStateMachine, SpineStateMachine, spine_state_machine};
+    use crate::{SpineEvent, SpineTrackEntryId, SpineTrackInfo, test_support::spineboy};
+
+    fn complete(entity: Entity, animation: &str) -> SpineEvent {
+        SpineEvent::Complete {
+            entity,
+            animation: animation.into(),
+            track: SpineTrackInfo {
+                id: SpineTrackEntryId(0),
+                track_index: 0,
+                track_time: 1.,
+                animation_duration: 1.,
+                loop_count: 0,
+            },
+        }
+    }
+
+    fn current_animation(world: &World, entity: Entity) -> String {
+        world
+            .get::<crate::Spine>(entity)
+            .unwrap()
+            .animation_state
+            .get_current(0)
+            .unwrap()
+            .animation()
+            .name()
+            .to_owned()
+    }
+
+    #[test]
+    fn exit_on_complete() {
+        let state_machine: AnimationStateMachine = ron::from_str(
+            r#"(
+                initial: "jump",
+                states: {
+                    "jump": (tracks: [(track: 0, animation: "jump")]),
+                    "idle": (tracks: [(track: 0, animation: "idle", looping: true)]),
+                },
+                transitions: [(from: "jump", to: "idle", mix: 0.2, exit_on_complete: true)],
+            )"#,
+        )
+        .unwrap();
+        let mut world = World::new();
+        world.init_resource::<Messages<SpineEvent>>();
+        world.init_resource::<Messages<AssetEvent<AnimationStateMachine>>>();
+        let mut state_machines = Assets::<AnimationStateMachine>::default();
+        let handle = state_machines.add(state_machine);
+        world.insert_resource(state_machines);
+        let entity = world
+            .spawn((spineboy(), SpineStateMachine::new(handle)))
+            .id();
+
+        let state = |world: &World| {
+            world
+                .get::<SpineStateMachine>(entity)
+                .unwrap()
+                .state()
+                .map(str::to_owned)
+        };
+        world.run_system_once(spine_state_machine).unwrap();
+        assert_eq!(state(&world).as_deref(), Some("jump"));
+        world.run_system_once(spine_state_machine).unwrap();
+        assert_eq!(state(&world).as_deref(), Some("jump"));
+
+        // only the current state's animations complete it
+        world.write_message(complete(entity, "run"));
+        world.run_system_once(spine_state_machine).unwrap();
+        assert_eq!(state(&world).as_deref(), Some("jump"));
+
+        world.write_message(complete(entity, "jump"));
+        world.run_system_once(spine_state_machine).unwrap();
+        assert_eq!(state(&world).as_deref(), Some("idle"));
+        assert_eq!(current_animation(&world, entity), "idle");
+    }
+}
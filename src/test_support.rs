@@ -0,0 +1,36 @@
+//! Fixtures shared by unit tests.
+
+use std::sync::Arc;
+
+use rusty_spine::{AnimationStateData, Atlas, SkeletonData, SkeletonJson};
+
+use crate::{SkeletonController, Spine};
+
+/// The skeleton data of spineboy, read directly from the example assets.
+pub(crate) fn spineboy_data() -> Arc<SkeletonData> {
+    let dir = "assets/spineboy/export";
+    let atlas = Atlas::new(
+        &std::fs::read(format!("{dir}/spineboy.atlas")).unwrap(),
+        dir,
+    )
+    .unwrap();
+    let skeleton_data = SkeletonJson::new(Arc::new(atlas))
+        .read_skeleton_data(&std::fs::read(format!("{dir}/spineboy-pro.json")).unwrap())
+        .unwrap();
+    Arc::new(skeleton_data)
+}
+
+/// Spineboy, with a default mix of 0.2 seconds.
+pub(crate) fn spineboy() -> Spine {
+    spine(spineboy_data())
+}
+
+/// A [`Spine`] of some skeleton data, with a default mix of 0.2 seconds.
+pub(crate) fn spine(skeleton_data: Arc<SkeletonData>) -> Spine {
+    let mut animation_state_data = AnimationStateData::new(skeleton_data.clone());
+    animation_state_data.set_default_mix(0.2);
+    Spine(SkeletonController::new(
+        skeleton_data,
+        Arc::new(animation_state_data),
+    ))
+}
@@ -132,6 +132,7 @@ impl Plugin for SpineUiPlugin {
                     resolve_spine_ui_render_layer_conflicts,
                     update_spine_ui_content_size,
                     sync_spine_ui_proxies,
+                    sync_spine_ui_crossfades,
                     forward_spine_ui_ready_events,
                     sync_spine_ui_animation_changes,
                     cleanup_spine_ui_proxies,
@@ -449,6 +450,17 @@ fn update_spine_ui_content_size(
     }
 }
 
+fn sync_spine_ui_crossfades(
+    nodes: Query<(&Crossfades, &SpineUiProxy), Changed<Crossfades>>,
+    mut proxy_query: Query<&mut Crossfades, Without<SpineUiProxy>>,
+) {
+    for (crossfades, proxy) in &nodes {
+        if let Ok(mut proxy_crossfades) = proxy_query.get_mut(proxy.proxy_entity) {
+            *proxy_crossfades = crossfades.clone();
+        }
+    }
+}
+
 fn sync_spine_ui_proxies(
     mut nodes: Query<(
         &ComputedNode,